@@ -1,3 +1,45 @@
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
 fn main() {
+    write_kubo_manifest();
     tauri_build::build()
 }
+
+/// 为 resources/bin 中的 Kubo 生成 SHA-256 清单（kubo.sha256）
+/// 运行时在执行任何 Kubo 命令前按此清单校验，防止打包后的二进制被替换或损坏
+fn write_kubo_manifest() {
+    let bin = Path::new("resources/bin");
+    println!("cargo:rerun-if-changed=resources/bin");
+    let Ok(entries) = fs::read_dir(bin) else {
+        println!("cargo:warning=resources/bin not found, Kubo will not be bundled");
+        return;
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("kubo-"))
+        .collect();
+    names.sort();
+
+    let mut manifest = String::new();
+    for name in names {
+        let mut file = File::open(bin.join(&name)).expect("Failed to open Kubo binary");
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher).expect("Failed to hash Kubo binary");
+        let hash: String = hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        manifest.push_str(&format!("{}  {}\n", hash, name));
+    }
+
+    // 内容不变时不重写，避免每次构建都触发 rerun
+    let path = bin.join("kubo.sha256");
+    if fs::read_to_string(&path).ok().as_deref() != Some(manifest.as_str()) {
+        fs::write(&path, manifest).expect("Failed to write kubo.sha256");
+    }
+}
@@ -2,7 +2,7 @@ use tauri::AppHandle;
 
 #[tauri::command]
 pub fn get_kubo_path(app: AppHandle) -> Result<String, String> {
-    let kubo_path = crate::helpers::paths::get_kubo_path(&app);
+    let kubo_path = crate::helpers::paths::get_kubo_path(&app).map_err(|e| e.to_string())?;
     Ok(kubo_path.to_string_lossy().to_string())
 }
 
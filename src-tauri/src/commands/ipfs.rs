@@ -1,10 +1,19 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
-use tracing::{error, info};
-
-use crate::ipfs::models::IpfsStateSnapshot;
-use crate::ipfs::state::{IpfsState, IpfsStateHandle};
+use tracing::{debug, error, info};
+
+use crate::ipfs;
+use crate::ipfs::backend::IpfsBackend;
+use crate::ipfs::logs::{DaemonLogsHandle, IpfsLogLine, LogFilter};
+use crate::ipfs::metrics::{IpfsMetricsSample, MetricsHandle};
+use crate::ipfs::models::{IpfsPeer, IpfsPeeringPeer, IpfsRepoStat, IpfsStateSnapshot};
+use crate::ipfs::pins::{self, IpfsPinEntry, IpfsPinProgress};
+use crate::ipfs::lifecycle::DaemonLifecycle;
+use crate::ipfs::state::{IpfsState, IpfsStateHandle, IpfsStatusHandle};
+use crate::models::settings::AppSettings;
+use crate::store::PlanetStoreHandle;
 
 // ============================================================
 // Tauri Command: 获取 IPFS 状态
@@ -12,11 +21,9 @@ use crate::ipfs::state::{IpfsState, IpfsStateHandle};
 // ============================================================
 
 #[tauri::command]
-pub async fn ipfs_get_state(
-    state: State<'_, IpfsStateHandle>,
-) -> Result<IpfsStateSnapshot, String> {
-    let s = state.lock().await;
-    Ok(s.snapshot())
+pub fn ipfs_get_state(status: State<'_, IpfsStatusHandle>) -> Result<IpfsStateSnapshot, String> {
+    let status = status.read().map_err(|e| e.to_string())?;
+    Ok(status.clone())
 }
 
 // ============================================================
@@ -30,60 +37,9 @@ pub async fn ipfs_setup(
     app: AppHandle,
 ) -> Result<(), String> {
     info!("ipfs_setup command called");
-
-    // 标记操作中
-    {
-        let mut s = state.lock().await;
-        s.is_operating = true;
-        s.emit_state_changed(&app);
-    }
-
-    // Setup
-    let setup_result = {
-        let mut s = state.lock().await;
-        s.daemon.setup().await
-    };
-
-    if let Err(e) = setup_result {
-        let mut s = state.lock().await;
-        s.is_operating = false;
-        s.error_message = Some(format!("Setup failed: {}", e));
-        s.emit_state_changed(&app);
-        return Err(format!("Setup failed: {}", e));
-    }
-
-    // Launch
-    let launch_result = {
-        let mut s = state.lock().await;
-        s.daemon.launch().await
-    };
-
-    match launch_result {
-        Ok(()) => {
-            let mut s = state.lock().await;
-            s.online = true;
-            s.is_operating = false;
-            s.error_message = None;
-
-            if let Ok(info) = s.daemon.get_server_info().await {
-                s.server_info = Some(info);
-            }
-            if let Ok(size) = s.daemon.get_repo_size().await {
-                s.repo_size = Some(size);
-            }
-
-            s.emit_state_changed(&app);
-            Ok(())
-        }
-        Err(e) => {
-            let mut s = state.lock().await;
-            s.online = false;
-            s.is_operating = false;
-            s.error_message = Some(format!("Launch failed: {}", e));
-            s.emit_state_changed(&app);
-            Err(format!("Launch failed: {}", e))
-        }
-    }
+    ipfs::state::start(&state, &app, true)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // ============================================================
@@ -97,44 +53,9 @@ pub async fn ipfs_launch(
     app: AppHandle,
 ) -> Result<(), String> {
     info!("ipfs_launch command called");
-
-    {
-        let mut s = state.lock().await;
-        s.is_operating = true;
-        s.emit_state_changed(&app);
-    }
-
-    let result = {
-        let mut s = state.lock().await;
-        s.daemon.launch().await
-    };
-
-    match result {
-        Ok(()) => {
-            let mut s = state.lock().await;
-            s.online = true;
-            s.is_operating = false;
-            s.error_message = None;
-
-            if let Ok(info) = s.daemon.get_server_info().await {
-                s.server_info = Some(info);
-            }
-            if let Ok(size) = s.daemon.get_repo_size().await {
-                s.repo_size = Some(size);
-            }
-
-            s.emit_state_changed(&app);
-            Ok(())
-        }
-        Err(e) => {
-            let mut s = state.lock().await;
-            s.online = false;
-            s.is_operating = false;
-            s.error_message = Some(format!("Launch failed: {}", e));
-            s.emit_state_changed(&app);
-            Err(format!("Launch failed: {}", e))
-        }
-    }
+    ipfs::state::start(&state, &app, false)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // ============================================================
@@ -149,33 +70,23 @@ pub async fn ipfs_shutdown(
 ) -> Result<(), String> {
     info!("ipfs_shutdown command called");
 
-    {
-        let mut s = state.lock().await;
-        s.is_operating = true;
-        s.emit_state_changed(&app);
+    let mut s = state.lock().await;
+    if !s.daemon.is_managed() {
+        return Err("Shutdown is disabled for external IPFS nodes".into());
     }
+    s.transition(DaemonLifecycle::Stopping, &app)
+        .map_err(|e| e.to_string())?;
 
-    let result = {
-        let mut s = state.lock().await;
-        s.daemon.shutdown().await
-    };
-
-    match result {
+    match s.daemon.shutdown().await {
         Ok(()) => {
-            let mut s = state.lock().await;
-            s.online = false;
-            s.is_operating = false;
-            s.error_message = None;
             s.server_info = None;
-            s.emit_state_changed(&app);
-            Ok(())
+            s.transition(DaemonLifecycle::Offline, &app)
+                .map_err(|e| e.to_string())
         }
         Err(e) => {
-            let mut s = state.lock().await;
-            s.is_operating = false;
-            s.error_message = Some(format!("Shutdown failed: {}", e));
-            s.emit_state_changed(&app);
-            Err(format!("Shutdown failed: {}", e))
+            let message = format!("Shutdown failed: {}", e);
+            let _ = s.transition(DaemonLifecycle::Failed { message: message.clone() }, &app);
+            Err(message)
         }
     }
 }
@@ -201,9 +112,7 @@ pub async fn ipfs_gc(
         Ok(count) => {
             // GC 后刷新 repo size
             let mut s = state.lock().await;
-            if let Ok(size) = s.daemon.get_repo_size().await {
-                s.repo_size = Some(size);
-            }
+            s.refresh_repo_state().await;
             s.emit_state_changed(&app);
             Ok(count)
         }
@@ -222,23 +131,361 @@ pub async fn ipfs_gc(
 #[tauri::command]
 pub async fn ipfs_refresh_status(
     state: State<'_, IpfsStateHandle>,
+    status: State<'_, IpfsStatusHandle>,
     app: AppHandle,
 ) -> Result<IpfsStateSnapshot, String> {
-    let mut s = state.lock().await;
+    // 正在 setup / launch / shutdown 时直接返回最近的快照，不等待
+    let Ok(mut s) = state.try_lock() else {
+        let status = status.read().map_err(|e| e.to_string())?;
+        return Ok(status.clone());
+    };
 
-    // 检查是否在线
+    // 检查是否在线，只在状态不一致且转换合法时更新（Failed 离线时保留错误信息）
     let online = s.daemon.check_online().await;
-    s.online = online;
+    if online != s.is_online() {
+        let target = if online {
+            DaemonLifecycle::Online
+        } else {
+            DaemonLifecycle::Offline
+        };
+        if let Err(e) = s.transition(target, &app) {
+            debug!("IPFS status not refreshed: {}", e);
+        }
+    }
 
     if online {
         if let Ok(info) = s.daemon.get_server_info().await {
             s.server_info = Some(info);
         }
-        if let Ok(size) = s.daemon.get_repo_size().await {
-            s.repo_size = Some(size);
-        }
+        s.refresh_repo_state().await;
     }
 
     s.emit_state_changed(&app);
     Ok(s.snapshot())
-}
\ No newline at end of file
+}
+
+// ============================================================
+// Tauri Command: 修复仓库
+// 前端调用: invoke("ipfs_repair_repo")
+// 配置损坏时备份仓库并重新 init，保留 keystore；返回备份目录，之后需重新 setup
+// ============================================================
+
+#[tauri::command]
+pub async fn ipfs_repair_repo(
+    state: State<'_, IpfsStateHandle>,
+    app: AppHandle,
+) -> Result<String, String> {
+    info!("ipfs_repair_repo command called");
+    let mut s = state.lock().await;
+    if !s.daemon.is_managed() {
+        return Err("Repair is not available for external IPFS nodes".into());
+    }
+    if s.lifecycle.is_busy() {
+        return Err("IPFS is busy, try again later".into());
+    }
+
+    if s.is_online() {
+        s.transition(DaemonLifecycle::Stopping, &app)
+            .map_err(|e| e.to_string())?;
+        let _ = s.daemon.shutdown().await;
+        s.server_info = None;
+        s.transition(DaemonLifecycle::Offline, &app)
+            .map_err(|e| e.to_string())?;
+    }
+    // 修复即重新初始化仓库，期间处于 SettingUp；完成后需重新 setup
+    s.transition(DaemonLifecycle::SettingUp, &app)
+        .map_err(|e| e.to_string())?;
+
+    // 复制、删除仓库和 ipfs init 都是阻塞操作，放到阻塞线程池执行
+    let repo_path = crate::helpers::paths::get_ipfs_repo_path(&app);
+    let repair_app = app.clone();
+    let result = tokio::task::spawn_blocking(move || ipfs::repo::repair(&repair_app, &repo_path))
+        .await
+        .unwrap_or_else(|e| Err(e.into()));
+    match result {
+        Ok(backup) => {
+            let _ = s.transition(DaemonLifecycle::Uninitialized, &app);
+            Ok(backup.to_string_lossy().to_string())
+        }
+        Err(e) => {
+            error!("Repo repair failed: {}", e);
+            let message = format!("Repair failed: {}", e);
+            let _ = s.transition(DaemonLifecycle::Failed { message: message.clone() }, &app);
+            Err(message)
+        }
+    }
+}
+
+// ============================================================
+// Tauri Command: 切换网络配置方案
+// 前端调用: invoke("ipfs_set_network_profile", { name })
+// 写入仓库配置后保存到设置；daemon 运行中时需重启才会生效
+// ============================================================
+
+#[tauri::command]
+pub async fn ipfs_set_network_profile(
+    state: State<'_, IpfsStateHandle>,
+    app: AppHandle,
+    name: String,
+) -> Result<(), String> {
+    info!("ipfs_set_network_profile command called: {}", name);
+    let mut settings = AppSettings::current(&app);
+    settings.network_profile = name.clone();
+    let profile = settings
+        .active_network_profile()
+        .map_err(|e| e.to_string())?
+        .clone();
+
+    let s = state.lock().await;
+    if !s.daemon.is_managed() {
+        return Err("Network profiles are not available for external IPFS nodes".into());
+    }
+    if s.lifecycle.is_busy() {
+        return Err("IPFS is busy, try again later".into());
+    }
+    ipfs::profile::apply(&app, &profile).map_err(|e| e.to_string())?;
+    drop(s);
+
+    AppSettings::update(&app, |s| s.network_profile = name).map_err(|e| e.to_string())
+}
+
+// ============================================================
+// Tauri Command: 仓库与版本信息
+// 前端调用: invoke("ipfs_repo_stat")
+// ============================================================
+
+#[tauri::command]
+pub async fn ipfs_repo_stat(
+    state: State<'_, IpfsStateHandle>,
+) -> Result<IpfsRepoStat, String> {
+    let s = state.lock().await;
+    s.daemon
+        .repo_stat(s.is_online())
+        .await
+        .map_err(|e| format!("Failed to read repo stat: {}", e))
+}
+
+// ============================================================
+// Tauri Command: Daemon 日志
+// 前端调用: invoke("ipfs_get_logs", { filter }) / invoke("ipfs_save_logs", { path })
+// 实时日志通过 "ipfs:log" 事件推送
+// ============================================================
+
+#[tauri::command]
+pub fn ipfs_get_logs(
+    logs: State<'_, DaemonLogsHandle>,
+    filter: Option<LogFilter>,
+) -> Result<Vec<IpfsLogLine>, String> {
+    Ok(logs.query(&filter.unwrap_or_default()))
+}
+
+/// 保存日志到文件，未指定路径时写入应用日志目录，返回实际路径
+#[tauri::command]
+pub fn ipfs_save_logs(
+    app: AppHandle,
+    logs: State<'_, DaemonLogsHandle>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => app
+            .path()
+            .app_log_dir()
+            .map_err(|e| e.to_string())?
+            .join(format!(
+                "kubo-{}.log",
+                chrono::Utc::now().format("%Y%m%d-%H%M%S")
+            )),
+    };
+    logs.save(&path).map_err(|e| e.to_string())?;
+    info!("Saved IPFS daemon logs to {:?}", path);
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ============================================================
+// Tauri Command: 带宽与 Peer 指标历史
+// 前端调用: invoke("ipfs_get_metrics")
+// 新采样通过 "ipfs:metrics" 事件推送
+// ============================================================
+
+#[tauri::command]
+pub fn ipfs_get_metrics(
+    metrics: State<'_, MetricsHandle>,
+) -> Result<Vec<IpfsMetricsSample>, String> {
+    Ok(metrics.history())
+}
+
+// ============================================================
+// Tauri Command: Swarm Peer 查看与连接
+// 前端调用: invoke("ipfs_swarm_peers") / invoke("ipfs_swarm_connect", { addr })
+//          invoke("ipfs_swarm_disconnect", { addr }) / invoke("ipfs_peering_peers")
+// ============================================================
+
+#[tauri::command]
+pub async fn ipfs_swarm_peers(
+    state: State<'_, IpfsStateHandle>,
+) -> Result<Vec<IpfsPeer>, String> {
+    let s = state.lock().await;
+    if !s.is_online() {
+        return Err("IPFS daemon is not online".into());
+    }
+    s.daemon.swarm_peers_verbose().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn ipfs_swarm_connect(
+    state: State<'_, IpfsStateHandle>,
+    addr: String,
+) -> Result<Vec<String>, String> {
+    let s = state.lock().await;
+    if !s.is_online() {
+        return Err("IPFS daemon is not online".into());
+    }
+    s.daemon
+        .swarm_connect(&addr)
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", addr, e))
+}
+
+#[tauri::command]
+pub async fn ipfs_swarm_disconnect(
+    state: State<'_, IpfsStateHandle>,
+    addr: String,
+) -> Result<Vec<String>, String> {
+    let s = state.lock().await;
+    if !s.is_online() {
+        return Err("IPFS daemon is not online".into());
+    }
+    s.daemon
+        .swarm_disconnect(&addr)
+        .await
+        .map_err(|e| format!("Failed to disconnect from {}: {}", addr, e))
+}
+
+#[tauri::command]
+pub async fn ipfs_peering_peers(
+    state: State<'_, IpfsStateHandle>,
+) -> Result<Vec<IpfsPeeringPeer>, String> {
+    let s = state.lock().await;
+    if !s.is_online() {
+        return Err("IPFS daemon is not online".into());
+    }
+    s.daemon.peering_peers().await.map_err(|e| e.to_string())
+}
+
+// ============================================================
+// Tauri Command: 本地 Pin 管理
+// 前端调用: invoke("ipfs_pin_ls", { pinType }) / invoke("ipfs_pin_add", { cid })
+//          invoke("ipfs_pin_rm", { cid }) / invoke("ipfs_pin_check", { cid })
+// pin 进度通过 "ipfs:pin-progress" 事件推送
+// ============================================================
+
+#[tauri::command]
+pub async fn ipfs_pin_ls(
+    state: State<'_, IpfsStateHandle>,
+    store: State<'_, PlanetStoreHandle>,
+    app: AppHandle,
+    pin_type: Option<String>,
+) -> Result<Vec<IpfsPinEntry>, String> {
+    let pin_type = pin_type.unwrap_or_else(|| "recursive".into());
+    if !["recursive", "direct", "indirect", "all"].contains(&pin_type.as_str()) {
+        return Err(format!("Invalid pin type: {}", pin_type));
+    }
+
+    let client = ipfs::state::online_client(&state)
+        .await
+        .map_err(|e| e.to_string())?;
+    let pinned = client
+        .list_pins(&pin_type)
+        .await
+        .map_err(|e| format!("Failed to list pins: {}", e))?;
+    let owners = pins::collect_owners(&client, store.inner(), &app).await;
+
+    let mut entries: Vec<IpfsPinEntry> = pinned
+        .keys
+        .into_iter()
+        .map(|(cid, info)| IpfsPinEntry {
+            owner: owners.get(&cid).cloned(),
+            cid,
+            pin_type: info.pin_type,
+        })
+        .collect();
+    // 有归属的排在前面
+    entries.sort_by(|a, b| {
+        b.owner
+            .is_some()
+            .cmp(&a.owner.is_some())
+            .then_with(|| a.cid.cmp(&b.cid))
+    });
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn ipfs_pin_add(
+    state: State<'_, IpfsStateHandle>,
+    app: AppHandle,
+    cid: String,
+) -> Result<(), String> {
+    info!("ipfs_pin_add command called: {}", cid);
+    // pin 可能持续很久，只在取句柄时持有 IPFS 锁
+    let client = ipfs::state::online_client(&state)
+        .await
+        .map_err(|e| e.to_string())?;
+    let emit = |blocks: u64, done: bool| {
+        let progress = IpfsPinProgress {
+            cid: cid.clone(),
+            blocks,
+            done,
+        };
+        if let Err(e) = app.emit("ipfs:pin-progress", &progress) {
+            error!("Failed to emit pin progress: {}", e);
+        }
+    };
+    let blocks = AtomicU64::new(0);
+    client
+        .pin_with_progress(&cid, |n| {
+            blocks.store(n, Ordering::Relaxed);
+            emit(n, false);
+        })
+        .await
+        .map_err(|e| format!("Failed to pin {}: {}", cid, e))?;
+    emit(blocks.load(Ordering::Relaxed), true);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn ipfs_pin_rm(
+    state: State<'_, IpfsStateHandle>,
+    cid: String,
+) -> Result<(), String> {
+    info!("ipfs_pin_rm command called: {}", cid);
+    let client = ipfs::state::online_client(&state)
+        .await
+        .map_err(|e| e.to_string())?;
+    client
+        .unpin(&cid)
+        .await
+        .map_err(|e| format!("Failed to unpin {}: {}", cid, e))
+}
+
+/// 检查 CID 是否已 pin；未 pin 时返回 null
+#[tauri::command]
+pub async fn ipfs_pin_check(
+    state: State<'_, IpfsStateHandle>,
+    store: State<'_, PlanetStoreHandle>,
+    app: AppHandle,
+    cid: String,
+) -> Result<Option<IpfsPinEntry>, String> {
+    let client = ipfs::state::online_client(&state)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(pin_type) = client.pin_type(&cid).await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let owners = pins::collect_owners(&client, store.inner(), &app).await;
+    Ok(Some(IpfsPinEntry {
+        owner: owners.get(&cid).cloned(),
+        cid,
+        pin_type,
+    }))
+}
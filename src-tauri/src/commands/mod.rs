@@ -1,4 +1,5 @@
 pub mod ipfs;
 pub mod planet;
 pub mod article;
-pub mod app;
\ No newline at end of file
+pub mod app;
+pub mod settings;
\ No newline at end of file
@@ -1,9 +1,19 @@
+use std::collections::HashSet;
+use std::path::Path;
 use tauri::State;
+use tracing::warn;
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 
 use crate::store::{PlanetStoreHandle, PlanetStoreSnapshot};
 use crate::models::planet::MyPlanet;
+use crate::ipfs::backend::IpfsBackend;
+use crate::ipfs::state::IpfsStateHandle;
+use crate::ipfs;
+use crate::publish::{self, archive, PublishResult};
+use crate::ipfs::pinning::{PinStatus, PinningService};
+use crate::publish::filebase;
+use crate::publish::state::PublishStage;
 
 // ============================================================
 // 请求/响应类型
@@ -32,6 +42,10 @@ pub struct UpdatePlanetRequest {
     pub telegram_username: Option<String>,
     pub mastodon_username: Option<String>,
     pub discord_link: Option<String>,
+    pub filebase_enabled: Option<bool>,
+    pub filebase_pin_name: Option<String>,
+    pub filebase_api_token: Option<String>,
+    pub pinning_services: Option<Vec<PinningService>>,
 }
 
 // ============================================================
@@ -57,16 +71,20 @@ pub fn planet_list(
 }
 
 /// 创建 Planet
+/// IPFS 正在 setup/launch 时等待其完成；daemon 不在线时 IPNS key 留到上线后补生成
 #[tauri::command]
-pub fn planet_create(
+pub async fn planet_create(
     app: tauri::AppHandle,
     store: State<'_, PlanetStoreHandle>,
+    ipfs: State<'_, IpfsStateHandle>,
     request: CreatePlanetRequest,
 ) -> Result<MyPlanet, String> {
-    let mut store = store.lock().map_err(|e| e.to_string())?;
-    let planet = store
-        .create_planet(request.name, request.about, request.template_name, &app)
+    let client = ipfs::state::online_client(&ipfs).await.ok();
+    let planet = MyPlanet::create(request.name, request.about, request.template_name, client.as_ref(), &app)
+        .await
         .map_err(|e| e.to_string())?;
+    let mut store = store.lock().map_err(|e| e.to_string())?;
+    store.insert_planet(planet.clone());
     store.emit_state_changed(&app);
     Ok(planet)
 }
@@ -94,6 +112,13 @@ pub fn planet_update(
     request: UpdatePlanetRequest,
 ) -> Result<MyPlanet, String> {
     let uuid = Uuid::parse_str(&planet_id).map_err(|e| e.to_string())?;
+    // pin 状态按服务名记录，同一 Planet 内服务名必须唯一
+    if let Some(services) = &request.pinning_services {
+        let mut names = HashSet::new();
+        if let Some(duplicate) = services.iter().find(|s| !names.insert(s.name.as_str())) {
+            return Err(format!("Duplicate pinning service name: {}", duplicate.name));
+        }
+    }
     let mut store = store.lock().map_err(|e| e.to_string())?;
 
     store.update_planet(uuid, |planet| {
@@ -124,6 +149,31 @@ pub fn planet_update(
         if let Some(discord) = request.discord_link {
             planet.discord_link = Some(discord);
         }
+        if let Some(enabled) = request.filebase_enabled {
+            planet.filebase_enabled = Some(enabled);
+        }
+        if let Some(pin_name) = request.filebase_pin_name {
+            planet.filebase_pin_name = Some(pin_name);
+        }
+        if let Some(token) = request.filebase_api_token {
+            planet.filebase_api_token = Some(token);
+        }
+        if let Some(mut services) = request.pinning_services {
+            // 同名同地址的服务保留已记录的 pin 状态
+            for service in services.iter_mut() {
+                if let Some(old) = planet
+                    .pinning_services
+                    .iter()
+                    .find(|s| s.name == service.name && s.endpoint == service.endpoint)
+                {
+                    service.request_id = old.request_id.clone();
+                    service.pin_cid = old.pin_cid.clone();
+                    service.pin_status = old.pin_status.clone();
+                    service.pin_status_retrieved = old.pin_status_retrieved;
+                }
+            }
+            planet.pinning_services = services;
+        }
     }, &app).map_err(|e| e.to_string())?;
 
     let planet = store.get_planet(uuid).cloned()
@@ -133,15 +183,193 @@ pub fn planet_update(
 }
 
 /// 删除 Planet
+/// 同时移除对应的 IPNS key；daemon 不在线或移除失败时记录下来，上线后由 ensure_planet_keys 清理
 #[tauri::command]
-pub fn planet_delete(
+pub async fn planet_delete(
     app: tauri::AppHandle,
     store: State<'_, PlanetStoreHandle>,
+    ipfs: State<'_, IpfsStateHandle>,
     planet_id: String,
 ) -> Result<(), String> {
     let uuid = Uuid::parse_str(&planet_id).map_err(|e| e.to_string())?;
+    let key_name = {
+        let store = store.lock().map_err(|e| e.to_string())?;
+        let planet = store
+            .get_planet(uuid)
+            .ok_or_else(|| format!("Planet not found: {}", planet_id))?;
+        planet.has_ipns_key().then(|| planet.key_name())
+    };
+
+    if let Some(key_name) = key_name {
+        let removed = match ipfs::state::online_client(&ipfs).await {
+            Ok(client) => client.remove_key(&key_name).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = removed {
+            warn!("IPNS key of planet {} is kept for later removal: {}", uuid, e);
+            if let Err(e) = ipfs::keys::record_orphan(&app, &key_name) {
+                warn!("Failed to record IPNS key {}: {}", key_name, e);
+            }
+        }
+    }
+
     let mut store = store.lock().map_err(|e| e.to_string())?;
     store.delete_planet(uuid, &app).map_err(|e| e.to_string())?;
     store.emit_state_changed(&app);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 发布 Planet：渲染 → 添加到 IPFS → 发布到 IPNS
+/// 发布进度通过 "planet:publish-state-changed" 事件推送；同一 Planet 重复发布会被拒绝
+/// 站点 CID 未变化时跳过 IPNS 发布，返回结果中 published 为 false
+#[tauri::command]
+pub async fn planet_publish(
+    app: tauri::AppHandle,
+    store: State<'_, PlanetStoreHandle>,
+    ipfs: State<'_, IpfsStateHandle>,
+    planet_id: String,
+) -> Result<PublishResult, String> {
+    let uuid = Uuid::parse_str(&planet_id).map_err(|e| e.to_string())?;
+    let planet = {
+        let mut store = store.lock().map_err(|e| e.to_string())?;
+        store.begin_publish(uuid, &app).map_err(|e| e.to_string())?;
+        store
+            .get_planet(uuid)
+            .cloned()
+            .ok_or_else(|| format!("Planet not found: {}", planet_id))?
+    };
+
+    let store_handle = store.inner().clone();
+    let on_stage = |stage: PublishStage| {
+        if let Ok(mut store) = store_handle.lock() {
+            store.set_publish_stage(uuid, stage, &app);
+        }
+    };
+
+    let result = match ipfs::state::online_client(&ipfs).await {
+        Ok(client) => publish::publish_planet(&planet, &client, &app, on_stage).await,
+        Err(e) => Err(e),
+    };
+
+    let handle = store.inner().clone();
+    let mut store = store.lock().map_err(|e| e.to_string())?;
+    match result {
+        Ok(result) if !result.published => {
+            store.set_publish_stage(uuid, PublishStage::Done, &app);
+            Ok(result)
+        }
+        Ok(result) => match store.record_published(uuid, result.cid.clone(), &app) {
+            Ok(()) => {
+                store.set_publish_stage(uuid, PublishStage::Done, &app);
+                store.emit_state_changed(&app);
+                if let Some(published) = store.get_planet(uuid).cloned() {
+                    publish::remote_pin::spawn_remote_pins(
+                        app.clone(),
+                        handle.clone(),
+                        published,
+                        result.cid.clone(),
+                    );
+                }
+                publish::prewarm::spawn_prewarm(app.clone(), handle, planet, result.cid.clone());
+                Ok(result)
+            }
+            Err(e) => {
+                let message = format!("Failed to record publish result: {}", e);
+                store.fail_publish(uuid, message.clone(), &app);
+                Err(message)
+            }
+        },
+        Err(e) => {
+            let message = format!("Publish failed: {}", e);
+            store.fail_publish(uuid, message.clone(), &app);
+            Err(message)
+        }
+    }
+}
+
+/// 将 Planet 最近一次发布的内容导出为 CAR 归档（{planet_id}.planet 目录），返回归档路径
+#[tauri::command]
+pub async fn planet_export_car(
+    store: State<'_, PlanetStoreHandle>,
+    ipfs: State<'_, IpfsStateHandle>,
+    planet_id: String,
+    target_dir: String,
+) -> Result<String, String> {
+    let uuid = Uuid::parse_str(&planet_id).map_err(|e| e.to_string())?;
+    let planet = {
+        let store = store.lock().map_err(|e| e.to_string())?;
+        store
+            .get_planet(uuid)
+            .cloned()
+            .ok_or_else(|| format!("Planet not found: {}", planet_id))?
+    };
+
+    let client = ipfs::state::online_client(&ipfs)
+        .await
+        .map_err(|e| e.to_string())?;
+    let bundle = archive::export(&planet, &client, Path::new(&target_dir))
+        .await
+        .map_err(|e| format!("Export failed: {}", e))?;
+    Ok(bundle.to_string_lossy().to_string())
+}
+
+/// 导入 CAR 归档：pin 归档内容并还原 Planet
+#[tauri::command]
+pub async fn planet_import_car(
+    app: tauri::AppHandle,
+    store: State<'_, PlanetStoreHandle>,
+    ipfs: State<'_, IpfsStateHandle>,
+    path: String,
+) -> Result<MyPlanet, String> {
+    let client = ipfs::state::online_client(&ipfs)
+        .await
+        .map_err(|e| e.to_string())?;
+    let planet = archive::import(Path::new(&path), &client, &app)
+        .await
+        .map_err(|e| format!("Import failed: {}", e))?;
+    {
+        let mut store = store.lock().map_err(|e| e.to_string())?;
+        store.insert_planet(planet.clone());
+        store.emit_state_changed(&app);
+    }
+    // 原 IPNS key 不在本机时补生成新 key
+    ipfs::state::ensure_planet_keys(&client, &app).await;
+    Ok(planet)
+}
+
+/// 刷新 Planet 在 Filebase 上的 pin 状态
+#[tauri::command]
+pub async fn planet_filebase_status(
+    app: tauri::AppHandle,
+    store: State<'_, PlanetStoreHandle>,
+    planet_id: String,
+) -> Result<PinStatus, String> {
+    let uuid = Uuid::parse_str(&planet_id).map_err(|e| e.to_string())?;
+    let planet = {
+        let store = store.lock().map_err(|e| e.to_string())?;
+        store
+            .get_planet(uuid)
+            .cloned()
+            .ok_or_else(|| format!("Planet not found: {}", planet_id))?
+    };
+    let token = planet
+        .filebase_api_token
+        .as_deref()
+        .ok_or_else(|| "Filebase API token is not set".to_string())?;
+    let request_id = planet
+        .filebase_request_id
+        .as_deref()
+        .ok_or_else(|| "Planet has not been pinned to Filebase yet".to_string())?;
+
+    let status = filebase::client(token)
+        .get_pin(request_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut store = store.lock().map_err(|e| e.to_string())?;
+    store
+        .record_filebase_pin(uuid, &status, false, &app)
+        .map_err(|e| e.to_string())?;
+    store.emit_state_changed(&app);
+    Ok(status)
+}
@@ -0,0 +1,28 @@
+use tauri::{AppHandle, State};
+
+use crate::models::settings::{AppSettings, SettingsHandle};
+
+/// 获取应用设置
+#[tauri::command]
+pub fn settings_get(settings: State<SettingsHandle>) -> Result<AppSettings, String> {
+    let settings = settings.lock().map_err(|e| e.to_string())?;
+    Ok(settings.clone())
+}
+
+/// 更新应用设置并保存到磁盘
+/// 端口的 last_used 由 setup 写入，保留后端的值，不使用前端可能过期的副本
+#[tauri::command]
+pub fn settings_update(
+    app: AppHandle,
+    settings: State<SettingsHandle>,
+    mut new_settings: AppSettings,
+) -> Result<AppSettings, String> {
+    new_settings.validate().map_err(|e| e.to_string())?;
+    let mut settings = settings.lock().map_err(|e| e.to_string())?;
+    new_settings.swarm_port.last_used = settings.swarm_port.last_used;
+    new_settings.api_port.last_used = settings.api_port.last_used;
+    new_settings.gateway_port.last_used = settings.gateway_port.last_used;
+    new_settings.save(&app).map_err(|e| e.to_string())?;
+    *settings = new_settings;
+    Ok(settings.clone())
+}
@@ -1,2 +1,5 @@
 pub mod paths;
-pub mod net; 
\ No newline at end of file
+pub mod net;
+
+#[cfg(test)]
+pub mod test_server;
@@ -1,11 +1,14 @@
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
-/// 获取 Kubo 可执行文件路径（跨平台）
-pub fn get_kubo_path(app: &AppHandle) -> PathBuf {
+use crate::ipfs::binary::{self, KuboBinaryError};
+use crate::models::settings::AppSettings;
+
+/// 打包资源目录（Kubo 及迁移程序所在位置）
+pub fn get_resource_dir(app: &AppHandle) -> PathBuf {
     // 在开发模式下，使用 target/debug/resources
     // 在生产模式下，使用 app.path().resource_dir()
-    let resource_dir = if cfg!(debug_assertions) {
+    if cfg!(debug_assertions) {
         // 开发模式：target/debug/resources
         std::env::current_exe()
             .ok()
@@ -22,44 +25,51 @@ pub fn get_kubo_path(app: &AppHandle) -> PathBuf {
         app.path()
             .resource_dir()
             .expect("Failed to get resource dir")
-    };
-
-    #[cfg(target_os = "windows")]
-    {
-        #[cfg(target_arch = "x86_64")]
-        {
-            resource_dir.join("bin").join("kubo-windows-amd64.exe")
-        }
-        #[cfg(not(target_arch = "x86_64"))]
-        {
-            panic!("Unsupported Windows architecture")
-        }
     }
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        #[cfg(target_arch = "aarch64")]
-        {
-            resource_dir.join("bin").join("kubo-darwin-arm64")
-        }
-        #[cfg(target_arch = "x86_64")]
-        {
-            resource_dir.join("bin").join("kubo-darwin-amd64")
-        }
-        #[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
-        {
-            panic!("Unsupported macOS architecture")
-        }
-    }
+/// 随应用打包的 fs-repo-migrations 程序目录
+/// 执行 Kubo 命令时加入 PATH，使 `ipfs repo migrate` 无需联网下载
+pub fn get_migrations_path(app: &AppHandle) -> PathBuf {
+    get_resource_dir(app).join("bin").join("migrations")
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        resource_dir.join("bin").join("kubo-linux-amd64")
-    }
+/// 覆盖 Kubo 可执行文件路径的环境变量
+pub const KUBO_PATH_ENV: &str = "PLANET_KUBO_PATH";
+
+/// 环境变量 PLANET_KUBO_PATH 指定的 Kubo 可执行文件（开发用，不做校验）
+pub fn get_kubo_env_override() -> Option<PathBuf> {
+    std::env::var_os(KUBO_PATH_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 用户指定的 Kubo 可执行文件：环境变量优先，其次是设置中的 kubo_binary_path
+pub fn get_kubo_override(app: &AppHandle) -> Option<PathBuf> {
+    get_kubo_env_override().or_else(|| {
+        AppSettings::current(app)
+            .kubo_binary_path
+            .filter(|p| !p.trim().is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// 随应用打包的 Kubo 可执行文件路径（跨平台）
+pub fn get_bundled_kubo_path(app: &AppHandle) -> Result<PathBuf, KuboBinaryError> {
+    let name = binary::kubo_binary_name(std::env::consts::OS, std::env::consts::ARCH)?;
+    Ok(get_resource_dir(app).join("bin").join(name))
+}
+
+/// 打包 Kubo 的 SHA-256 清单
+pub fn get_kubo_manifest_path(app: &AppHandle) -> PathBuf {
+    get_resource_dir(app).join("bin").join(binary::MANIFEST_FILE)
+}
 
-    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
-    {
-        panic!("Unsupported operating system")
+/// 获取 Kubo 可执行文件路径：自定义路径优先，否则使用打包的 Kubo
+pub fn get_kubo_path(app: &AppHandle) -> Result<PathBuf, KuboBinaryError> {
+    match get_kubo_override(app) {
+        Some(path) => Ok(path),
+        None => get_bundled_kubo_path(app),
     }
 }
 
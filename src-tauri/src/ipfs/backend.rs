@@ -0,0 +1,767 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::io::AsyncReadExt;
+use tracing::{error, info};
+
+use super::command::{KuboCommand, ADD_CHUNKER};
+use super::repo;
+use crate::models::settings::{KuboAuth, KuboNode};
+use super::models::{
+    IpfsBandwidth, IpfsPublished, IpfsRepoState, IpfsResolved, IpnsPublishOptions,
+};
+
+// ============================================================
+// IPFS 后端抽象
+// 发布等流程只依赖 IpfsBackend，不直接依赖 Kubo 进程
+// 实现：KuboCliBackend（spawn kubo）、KuboHttpBackend（HTTP RPC）、
+//      IpfsDaemon（在线走 HTTP、离线回退 CLI），以及测试用的 memory::MemoryBackend
+// ============================================================
+
+/// keystore 中的一个密钥
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpfsKey {
+    pub name: String,
+    /// Key ID，即 IPNS 名称
+    pub id: String,
+}
+
+pub trait IpfsBackend: Send + Sync {
+    /// 递归添加目录（含隐藏文件）并 pin，返回根 CID
+    fn add_directory(&self, dir: &Path) -> impl Future<Output = Result<String>> + Send;
+
+    /// 只计算目录 CID，不写入仓库；参数与 add_directory 一致
+    fn hash_directory(&self, dir: &Path) -> impl Future<Output = Result<String>> + Send;
+
+    /// 读取 /ipfs/{cid}/{path} 的内容
+    fn cat(&self, path: &str) -> impl Future<Output = Result<Vec<u8>>> + Send;
+
+    /// 生成密钥，返回 Key ID
+    fn generate_key(&self, name: &str) -> impl Future<Output = Result<String>> + Send;
+
+    fn remove_key(&self, name: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// 列出密钥（不含 "self"）
+    fn list_keys(&self) -> impl Future<Output = Result<Vec<IpfsKey>>> + Send;
+
+    /// 将 CID 发布到 key_name 对应的 IPNS 名称
+    fn publish_name(
+        &self,
+        key_name: &str,
+        cid: &str,
+        options: IpnsPublishOptions,
+    ) -> impl Future<Output = Result<IpfsPublished>> + Send;
+
+    /// 解析 IPNS 名称，返回 CID
+    fn resolve_name(&self, name: &str) -> impl Future<Output = Result<String>> + Send;
+
+    fn pin(&self, cid: &str) -> impl Future<Output = Result<()>> + Send;
+
+    fn unpin(&self, cid: &str) -> impl Future<Output = Result<()>> + Send;
+
+    fn stats_bw(&self) -> impl Future<Output = Result<IpfsBandwidth>> + Send;
+
+    fn repo_state(&self) -> impl Future<Output = Result<IpfsRepoState>> + Send;
+}
+
+/// 将 API multiaddr 转换为 URL，如 /dns4/ipfs.example.com/tcp/443/https → https://ipfs.example.com:443
+pub fn api_url(multiaddr: &str) -> Option<String> {
+    let address = repo::api_address(multiaddr)?;
+    let secure = multiaddr
+        .trim()
+        .split('/')
+        .any(|part| part == "https" || part == "tls");
+    let scheme = if secure { "https" } else { "http" };
+    Some(format!("{}://{}", scheme, address))
+}
+
+/// 去掉 /ipfs/ 前缀
+fn strip_ipfs_prefix(path: &str) -> Result<String> {
+    path.strip_prefix("/ipfs/")
+        .map(|cid| cid.to_string())
+        .ok_or_else(|| anyhow!("Unexpected resolve result: {}", path))
+}
+
+// ============================================================
+// Kubo CLI 实现
+// ============================================================
+
+pub struct KuboCliBackend {
+    app: AppHandle,
+    node: KuboNode,
+}
+
+impl KuboCliBackend {
+    pub fn new(app: AppHandle, node: KuboNode) -> Self {
+        Self { app, node }
+    }
+
+    /// 执行命令，失败时带上 stderr
+    async fn run(&self, command: KuboCommand, action: &str) -> Result<String> {
+        let output = command.on_node(&self.node).run_async().await?;
+        if output.ret == 0 {
+            Ok(output.stdout)
+        } else {
+            Err(anyhow!("Failed to {}: {}", action, output.stderr.trim()))
+        }
+    }
+
+    async fn run_args(&self, args: &[&str], action: &str) -> Result<String> {
+        let args = args.iter().map(|a| a.to_string()).collect();
+        self.run(KuboCommand::with_args(self.app.clone(), args), action)
+            .await
+    }
+}
+
+impl IpfsBackend for KuboCliBackend {
+    async fn add_directory(&self, dir: &Path) -> Result<String> {
+        let dir = dir.to_string_lossy();
+        let stdout = self
+            .run(KuboCommand::add_directory(self.app.clone(), &dir), "add directory")
+            .await?;
+        Ok(stdout.trim().to_string())
+    }
+
+    async fn hash_directory(&self, dir: &Path) -> Result<String> {
+        let dir = dir.to_string_lossy();
+        let stdout = self
+            .run(
+                KuboCommand::get_directory_cid(self.app.clone(), &dir),
+                "get directory CID",
+            )
+            .await?;
+        Ok(stdout.trim().to_string())
+    }
+
+    async fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        let output = KuboCommand::with_args(self.app.clone(), vec!["cat".into(), path.into()])
+            .on_node(&self.node)
+            .run_raw_async()
+            .await?;
+        if output.status.success() {
+            // 保留原始字节，附件等二进制内容不能经过 UTF-8 解码
+            Ok(output.stdout)
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow!("Failed to cat {}: {}", path, stderr.trim()))
+        }
+    }
+
+    async fn generate_key(&self, name: &str) -> Result<String> {
+        let stdout = self
+            .run(KuboCommand::generate_key(self.app.clone(), name), "generate key")
+            .await?;
+        Ok(stdout.trim().to_string())
+    }
+
+    async fn remove_key(&self, name: &str) -> Result<()> {
+        self.run(KuboCommand::delete_key(self.app.clone(), name), "delete key")
+            .await?;
+        Ok(())
+    }
+
+    async fn list_keys(&self) -> Result<Vec<IpfsKey>> {
+        let stdout = self
+            .run(KuboCommand::list_keys_with_ids(self.app.clone()), "list keys")
+            .await?;
+        Ok(parse_key_list(&stdout))
+    }
+
+    async fn publish_name(
+        &self,
+        key_name: &str,
+        cid: &str,
+        options: IpnsPublishOptions,
+    ) -> Result<IpfsPublished> {
+        let stdout = self
+            .run_args(
+                &[
+                    "name",
+                    "publish",
+                    &format!("--key={}", key_name),
+                    "--allow-offline",
+                    &format!("--lifetime={}s", options.lifetime.as_secs()),
+                    &format!("--ttl={}s", options.ttl.as_secs()),
+                    "--enc=json",
+                    &format!("/ipfs/{}", cid),
+                ],
+                "publish IPNS name",
+            )
+            .await?;
+        Ok(serde_json::from_str(&stdout)?)
+    }
+
+    async fn resolve_name(&self, name: &str) -> Result<String> {
+        let stdout = self
+            .run_args(&["name", "resolve", "--enc=json", name], "resolve IPNS name")
+            .await?;
+        let resolved: IpfsResolved = serde_json::from_str(&stdout)?;
+        strip_ipfs_prefix(&resolved.path)
+    }
+
+    async fn pin(&self, cid: &str) -> Result<()> {
+        self.run_args(&["pin", "add", cid], "pin").await?;
+        Ok(())
+    }
+
+    async fn unpin(&self, cid: &str) -> Result<()> {
+        self.run_args(&["pin", "rm", cid], "unpin").await?;
+        Ok(())
+    }
+
+    async fn stats_bw(&self) -> Result<IpfsBandwidth> {
+        let stdout = self
+            .run_args(&["stats", "bw", "--enc=json"], "read bandwidth stats")
+            .await?;
+        Ok(serde_json::from_str(&stdout)?)
+    }
+
+    async fn repo_state(&self) -> Result<IpfsRepoState> {
+        let stdout = self
+            .run(KuboCommand::repo_stat(self.app.clone()), "read repo stat")
+            .await?;
+        Ok(serde_json::from_str(&stdout)?)
+    }
+}
+
+/// 解析 `ipfs key list -l` 的输出（每行 "{key_id} {name}"），排除 "self"
+pub fn parse_key_list(stdout: &str) -> Vec<IpfsKey> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(id), Some(name)) if name != "self" => Some(IpfsKey {
+                    name: name.to_string(),
+                    id: id.to_string(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+// ============================================================
+// Kubo HTTP RPC 实现
+// ============================================================
+
+#[derive(Clone)]
+pub struct KuboHttpBackend {
+    /// 如 http://127.0.0.1:5981
+    base_url: String,
+    client: Client,
+    auth: Option<KuboAuth>,
+}
+
+impl KuboHttpBackend {
+    pub fn new(base_url: String, client: Client) -> Self {
+        Self {
+            base_url,
+            client,
+            auth: None,
+        }
+    }
+
+    /// 本机 daemon 的 API
+    pub fn local(api_port: u16, client: Client) -> Self {
+        Self::new(format!("http://127.0.0.1:{}", api_port), client)
+    }
+
+    /// 外部节点的 API，api 为 multiaddr
+    pub fn external(api: &str, auth: Option<KuboAuth>, client: Client) -> Result<Self> {
+        let base_url =
+            api_url(api).ok_or_else(|| anyhow!("Unsupported Kubo API address: {}", api))?;
+        Ok(Self {
+            base_url,
+            client,
+            auth,
+        })
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.post(format!("{}/api/v0/{}", self.base_url, path));
+        match &self.auth {
+            Some(auth) => auth.apply(request),
+            None => request,
+        }
+    }
+
+    /// 发送 API 请求并检查状态码，返回未读取的响应（用于流式输出）
+    pub async fn request(
+        &self,
+        path: &str,
+        args: Option<&HashMap<String, String>>,
+        timeout_secs: Option<u64>,
+    ) -> Result<reqwest::Response> {
+        let mut request = self.post(path);
+        if let Some(params) = args {
+            request = request.query(params);
+        }
+        if let Some(t) = timeout_secs {
+            request = request.timeout(Duration::from_secs(t));
+        }
+
+        let response = request.send().await?;
+        check_status(path, response).await
+    }
+
+    /// 调用 API 并返回完整响应体
+    pub async fn call(
+        &self,
+        path: &str,
+        args: Option<&HashMap<String, String>>,
+        timeout_secs: Option<u64>,
+    ) -> Result<Vec<u8>> {
+        let response = self.request(path, args, timeout_secs).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn call_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        args: &[(&str, &str)],
+    ) -> Result<T> {
+        let args: HashMap<String, String> = args
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let data = self.call(path, Some(&args), None).await?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// 流式上传目录并报告进度，返回根 CID
+    pub async fn add_directory_with_progress(
+        &self,
+        dir: &Path,
+        on_progress: AddProgressFn,
+    ) -> Result<String> {
+        self.add(dir, false, Some(on_progress)).await
+    }
+
+    /// 以流式 multipart 上传目录，返回根 CID
+    /// 文件按块读取，不会一次性载入内存；进度按已发送的文件字节数计算
+    async fn add(
+        &self,
+        dir: &Path,
+        only_hash: bool,
+        on_progress: Option<AddProgressFn>,
+    ) -> Result<String> {
+        let (parts, root_name) = directory_parts(dir)?;
+        let mut query = vec![
+            ("cid-version", "1"),
+            ("chunker", ADD_CHUNKER),
+            ("pin", "true"),
+            ("quieter", "true"),
+        ];
+        if only_hash {
+            query.push(("only-hash", "true"));
+        }
+        let text = self
+            .post_multipart("add", &query, parts, on_progress)
+            .await?
+            .text()
+            .await?;
+        root_cid(&text, &root_name)
+    }
+
+    /// 导入 CAR 文件并 pin 其根，返回根 CID
+    pub async fn dag_import(&self, car: &Path) -> Result<Vec<String>> {
+        let part = AddPart {
+            name: car
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "import.car".into()),
+            path: Some(car.to_path_buf()),
+            size: fs::metadata(car)?.len(),
+        };
+        let text = self
+            .post_multipart("dag/import", &[("pin-roots", "true")], vec![part], None)
+            .await?
+            .text()
+            .await?;
+
+        let mut roots = Vec::new();
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let value: serde_json::Value = serde_json::from_str(line)?;
+            let Some(root) = value.get("Root") else {
+                continue;
+            };
+            let cid = root["Cid"]["/"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Unexpected dag/import response: {}", line))?;
+            match root["PinErrorMsg"].as_str() {
+                Some(msg) if !msg.is_empty() => {
+                    return Err(anyhow!("Failed to pin imported root {}: {}", cid, msg))
+                }
+                _ => roots.push(cid.to_string()),
+            }
+        }
+        Ok(roots)
+    }
+
+    /// 以流式 multipart 请求体调用 API
+    async fn post_multipart(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+        parts: Vec<AddPart>,
+        on_progress: Option<AddProgressFn>,
+    ) -> Result<reqwest::Response> {
+        let boundary = format!("planet-{}", uuid::Uuid::new_v4().simple());
+        let body = reqwest::Body::wrap_stream(multipart_stream(parts, boundary.clone(), on_progress));
+        let response = self
+            .post(path)
+            .query(query)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", boundary),
+            )
+            .timeout(Duration::from_secs(ADD_TIMEOUT_SECS))
+            .body(body)
+            .send()
+            .await?;
+        check_status(path, response).await
+    }
+}
+
+async fn check_status(path: &str, response: reqwest::Response) -> Result<reqwest::Response> {
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        error!("IPFS API error: {} {} - {}", path, status, body);
+        return Err(anyhow!("IPFS API error: {} {} - {}", path, status, body.trim()));
+    }
+    Ok(response)
+}
+
+impl IpfsBackend for KuboHttpBackend {
+    async fn add_directory(&self, dir: &Path) -> Result<String> {
+        self.add(dir, false, None).await
+    }
+
+    async fn hash_directory(&self, dir: &Path) -> Result<String> {
+        self.add(dir, true, None).await
+    }
+
+    async fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        let mut args = HashMap::new();
+        args.insert("arg".to_string(), path.to_string());
+        self.call("cat", Some(&args), Some(300)).await
+    }
+
+    async fn generate_key(&self, name: &str) -> Result<String> {
+        let key: serde_json::Value = self.call_json("key/gen", &[("arg", name)]).await?;
+        key["Id"]
+            .as_str()
+            .map(|id| id.to_string())
+            .ok_or_else(|| anyhow!("Unexpected key/gen response"))
+    }
+
+    async fn remove_key(&self, name: &str) -> Result<()> {
+        let _: serde_json::Value = self.call_json("key/rm", &[("arg", name)]).await?;
+        Ok(())
+    }
+
+    async fn list_keys(&self) -> Result<Vec<IpfsKey>> {
+        let list: serde_json::Value = self.call_json("key/list", &[("l", "true")]).await?;
+        let keys = list["Keys"].as_array().cloned().unwrap_or_default();
+        Ok(keys
+            .iter()
+            .filter_map(|k| {
+                let name = k["Name"].as_str()?;
+                (name != "self").then(|| IpfsKey {
+                    name: name.to_string(),
+                    id: k["Id"].as_str().unwrap_or_default().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    async fn publish_name(
+        &self,
+        key_name: &str,
+        cid: &str,
+        options: IpnsPublishOptions,
+    ) -> Result<IpfsPublished> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), format!("/ipfs/{}", cid));
+        args.insert("key".into(), key_name.into());
+        args.insert("allow-offline".into(), "true".into());
+        args.insert("lifetime".into(), format!("{}s", options.lifetime.as_secs()));
+        args.insert("ttl".into(), format!("{}s", options.ttl.as_secs()));
+        let data = self.call("name/publish", Some(&args), Some(600)).await?;
+        let published: IpfsPublished = serde_json::from_slice(&data)?;
+        info!("Published {} to IPNS name {}", published.value, published.name);
+        Ok(published)
+    }
+
+    async fn resolve_name(&self, name: &str) -> Result<String> {
+        let resolved: IpfsResolved = self.call_json("name/resolve", &[("arg", name)]).await?;
+        strip_ipfs_prefix(&resolved.path)
+    }
+
+    async fn pin(&self, cid: &str) -> Result<()> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), cid.into());
+        self.call("pin/add", Some(&args), Some(120)).await?;
+        Ok(())
+    }
+
+    async fn unpin(&self, cid: &str) -> Result<()> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), cid.into());
+        self.call("pin/rm", Some(&args), Some(120)).await?;
+        Ok(())
+    }
+
+    async fn stats_bw(&self) -> Result<IpfsBandwidth> {
+        self.call_json("stats/bw", &[]).await
+    }
+
+    async fn repo_state(&self) -> Result<IpfsRepoState> {
+        self.call_json("repo/stat", &[]).await
+    }
+}
+
+/// add 的进度回调，参数为 (已发送字节数, 总字节数)
+pub type AddProgressFn = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+/// 大站点或 CAR 上传可能持续较久，不使用 Client 的默认超时
+const ADD_TIMEOUT_SECS: u64 = 3600;
+
+/// 每次从文件读取的块大小
+const ADD_CHUNK_SIZE: usize = 256 * 1024;
+
+/// multipart 请求中的一项；path 为 None 表示目录
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddPart {
+    /// 相对于根目录父级的路径，如 "public/assets/a.css"
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub size: u64,
+}
+
+impl AddPart {
+    fn header(&self, boundary: &str) -> Vec<u8> {
+        let content_type = if self.path.is_some() {
+            "application/octet-stream"
+        } else {
+            "application/x-directory"
+        };
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            boundary,
+            url_encode(&self.name),
+            content_type
+        )
+        .into_bytes()
+    }
+}
+
+/// 列出 Kubo add 所需的 multipart 各项，返回 (各项, 根目录名)
+/// 深度优先，目录先于其内容出现；文件名按 Kubo 的约定整体 URL 编码
+pub fn directory_parts(dir: &Path) -> Result<(Vec<AddPart>, String)> {
+    let root_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| anyhow!("Invalid directory: {}", dir.display()))?;
+    let mut parts = Vec::new();
+    collect_parts(dir, &root_name, &mut parts)?;
+    Ok((parts, root_name))
+}
+
+fn collect_parts(dir: &Path, name: &str, parts: &mut Vec<AddPart>) -> Result<()> {
+    parts.push(AddPart {
+        name: name.to_string(),
+        path: None,
+        size: 0,
+    });
+    let mut children: Vec<_> = fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+    children.sort_by_key(|e| e.file_name());
+    for child in children {
+        let child_name = format!("{}/{}", name, child.file_name().to_string_lossy());
+        let file_type = child.file_type()?;
+        if file_type.is_dir() {
+            collect_parts(&child.path(), &child_name, parts)?;
+        } else if file_type.is_file() {
+            parts.push(AddPart {
+                name: child_name,
+                path: Some(child.path()),
+                size: child.metadata()?.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+struct MultipartState {
+    parts: VecDeque<AddPart>,
+    boundary: String,
+    file: Option<tokio::fs::File>,
+    sent: u64,
+    total: u64,
+    on_progress: Option<AddProgressFn>,
+    finished: bool,
+}
+
+/// 按需读取文件，逐块产出 multipart 请求体
+fn multipart_stream(
+    parts: Vec<AddPart>,
+    boundary: String,
+    on_progress: Option<AddProgressFn>,
+) -> impl futures_util::Stream<Item = std::io::Result<Vec<u8>>> + Send + 'static {
+    let state = MultipartState {
+        total: parts.iter().map(|p| p.size).sum(),
+        parts: parts.into(),
+        boundary,
+        file: None,
+        sent: 0,
+        on_progress,
+        finished: false,
+    };
+    futures_util::stream::unfold(state, |mut state| async move {
+        match next_chunk(&mut state).await {
+            Ok(Some(chunk)) => Some((Ok(chunk), state)),
+            Ok(None) => None,
+            Err(e) => {
+                state.finished = true;
+                state.parts.clear();
+                state.file = None;
+                Some((Err(e), state))
+            }
+        }
+    })
+}
+
+async fn next_chunk(state: &mut MultipartState) -> std::io::Result<Option<Vec<u8>>> {
+    if let Some(file) = state.file.as_mut() {
+        let mut buf = vec![0u8; ADD_CHUNK_SIZE];
+        let n = file.read(&mut buf).await?;
+        if n > 0 {
+            buf.truncate(n);
+            state.sent += n as u64;
+            if let Some(on_progress) = &state.on_progress {
+                on_progress(state.sent, state.total);
+            }
+            return Ok(Some(buf));
+        }
+        state.file = None;
+        return Ok(Some(b"\r\n".to_vec()));
+    }
+
+    match state.parts.pop_front() {
+        Some(part) => {
+            let mut chunk = part.header(&state.boundary);
+            match &part.path {
+                Some(path) => state.file = Some(tokio::fs::File::open(path).await?),
+                None => chunk.extend_from_slice(b"\r\n"),
+            }
+            Ok(Some(chunk))
+        }
+        None if !state.finished => {
+            state.finished = true;
+            Ok(Some(format!("--{}--\r\n", state.boundary).into_bytes()))
+        }
+        None => Ok(None),
+    }
+}
+
+fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 从 add 的逐行 JSON 输出中找到根目录的 CID
+fn root_cid(output: &str, root_name: &str) -> Result<String> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|v| v["Name"].as_str() == Some(root_name))
+        .filter_map(|v| v["Hash"].as_str().map(|h| h.to_string()))
+        .next_back()
+        .ok_or_else(|| anyhow!("add returned no root CID for {}", root_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_list_skips_self() {
+        let keys = parse_key_list("k51self self\nk51abc 1234-planet\n\n");
+        assert_eq!(
+            keys,
+            vec![IpfsKey {
+                name: "1234-planet".into(),
+                id: "k51abc".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_api_url_from_multiaddr() {
+        assert_eq!(
+            api_url("/ip4/192.168.1.10/tcp/5001").as_deref(),
+            Some("http://192.168.1.10:5001")
+        );
+        assert_eq!(
+            api_url("/dns4/ipfs.example.com/tcp/443/https").as_deref(),
+            Some("https://ipfs.example.com:443")
+        );
+        assert_eq!(api_url("/unix/tmp/api.sock"), None);
+    }
+
+    #[tokio::test]
+    async fn test_multipart_stream_lists_entries_in_order() {
+        use futures_util::StreamExt;
+
+        let dir = std::env::temp_dir().join(format!("planet-mp-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("assets")).unwrap();
+        fs::write(dir.join("index.html"), "<h1>hi</h1>").unwrap();
+        fs::write(dir.join("assets").join("a b.css"), "body{}").unwrap();
+
+        let (parts, root) = directory_parts(&dir).unwrap();
+        let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorder = progress.clone();
+        let on_progress: AddProgressFn = Arc::new(move |sent, total| {
+            recorder.lock().unwrap().push((sent, total));
+        });
+        let chunks: Vec<Vec<u8>> = multipart_stream(parts, "b".into(), Some(on_progress))
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        let body = String::from_utf8(chunks.concat()).unwrap();
+
+        let names: Vec<&str> = body
+            .lines()
+            .filter_map(|l| l.split("filename=\"").nth(1))
+            .map(|l| l.trim_end_matches('"'))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                root.clone(),
+                format!("{}%2Fassets", root),
+                format!("{}%2Fassets%2Fa%20b.css", root),
+                format!("{}%2Findex.html", root),
+            ]
+        );
+        assert!(body.contains("\r\n\r\nbody{}\r\n--b\r\n"));
+        assert!(body.ends_with("--b--\r\n"));
+        assert_eq!(*progress.lock().unwrap(), vec![(6, 17), (17, 17)]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,236 @@
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::AppHandle;
+use tracing::info;
+
+use crate::helpers::paths;
+use crate::models::settings::AppSettings;
+
+// ============================================================
+// Kubo 可执行文件解析与完整性校验
+// 打包的 Kubo 在执行任何 KuboCommand 前与 resources/bin/kubo.sha256 比对
+// 清单由 build.rs 在构建时根据 resources/bin/kubo-* 生成，为 sha256sum 的输出格式，每行 "{hex}  {文件名}"
+// 清单已加入 .gitignore，全新 checkout 在首次构建前没有清单，此时 Kubo 无法启动
+// 设置中的 kubo_binary_path 必须配合用户固定的 kubo_binary_sha256；只有环境变量 PLANET_KUBO_PATH 跳过校验
+// ============================================================
+
+/// 打包在 resources/bin 中的 SHA-256 清单文件名
+pub const MANIFEST_FILE: &str = "kubo.sha256";
+
+#[derive(Debug, thiserror::Error)]
+pub enum KuboBinaryError {
+    #[error("Kubo is not available for {os}-{arch}")]
+    UnsupportedPlatform { os: String, arch: String },
+    #[error("Kubo binary not found at {0}")]
+    NotFound(PathBuf),
+    #[error("Kubo checksum manifest not found at {0}")]
+    ManifestMissing(PathBuf),
+    #[error("Custom Kubo binary {0} requires a pinned sha256 (kubo_binary_sha256) in settings")]
+    PinnedHashMissing(PathBuf),
+    #[error("Kubo checksum manifest has no entry for {0}")]
+    ManifestEntryMissing(String),
+    #[error("Invalid line in Kubo checksum manifest: {0:?}")]
+    InvalidManifest(String),
+    #[error("Kubo binary {path} failed the integrity check (expected sha256 {expected}, got {actual})")]
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// 上次校验通过的文件（路径、大小、修改时间、期望的哈希），文件未变化时不重复计算哈希
+type Stamp = (PathBuf, u64, Option<SystemTime>, String);
+static VERIFIED: Mutex<Option<Stamp>> = Mutex::new(None);
+
+/// 当前平台对应的打包 Kubo 文件名
+pub fn kubo_binary_name(os: &str, arch: &str) -> Result<&'static str, KuboBinaryError> {
+    match (os, arch) {
+        ("windows", "x86_64") => Ok("kubo-windows-amd64.exe"),
+        ("macos", "aarch64") => Ok("kubo-darwin-arm64"),
+        ("macos", "x86_64") => Ok("kubo-darwin-amd64"),
+        ("linux", "x86_64") => Ok("kubo-linux-amd64"),
+        ("linux", "aarch64") => Ok("kubo-linux-arm64"),
+        _ => Err(KuboBinaryError::UnsupportedPlatform {
+            os: os.to_string(),
+            arch: arch.to_string(),
+        }),
+    }
+}
+
+/// 返回可以执行的 Kubo 路径
+/// PLANET_KUBO_PATH 只检查文件存在；设置中的自定义路径按用户固定的哈希校验；打包的 Kubo 按清单校验
+pub fn verified_path(app: &AppHandle) -> Result<PathBuf, KuboBinaryError> {
+    if let Some(path) = paths::get_kubo_env_override() {
+        if !path.is_file() {
+            return Err(KuboBinaryError::NotFound(path));
+        }
+        return Ok(path);
+    }
+    let settings = AppSettings::current(app);
+    if let Some(path) = settings
+        .kubo_binary_path
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from)
+    {
+        let expected = settings
+            .kubo_binary_sha256
+            .filter(|h| !h.trim().is_empty())
+            .ok_or_else(|| KuboBinaryError::PinnedHashMissing(path.clone()))?;
+        verify_sha256(&path, expected.trim())?;
+        return Ok(path);
+    }
+    let path = paths::get_bundled_kubo_path(app)?;
+    verify(&path, &paths::get_kubo_manifest_path(app))?;
+    Ok(path)
+}
+
+/// 在阻塞线程池中执行 verified_path
+/// 首次校验需要对约 100MB 的文件计算哈希，不能放在 async 运行时上；之后 run() 命中缓存
+pub async fn verified_path_async(app: &AppHandle) -> anyhow::Result<PathBuf> {
+    let app = app.clone();
+    Ok(tokio::task::spawn_blocking(move || verified_path(&app)).await??)
+}
+
+/// 按清单校验 Kubo 文件的 SHA-256
+pub fn verify(path: &Path, manifest: &Path) -> Result<(), KuboBinaryError> {
+    if !path.exists() {
+        return Err(KuboBinaryError::NotFound(path.to_path_buf()));
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let content = fs::read_to_string(manifest).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => KuboBinaryError::ManifestMissing(manifest.to_path_buf()),
+        _ => io_error(manifest, e),
+    })?;
+    let expected = manifest_entry(&content, &name)?
+        .ok_or_else(|| KuboBinaryError::ManifestEntryMissing(name.clone()))?;
+    verify_sha256(path, expected)
+}
+
+/// 校验文件的 SHA-256 是否与期望的哈希一致
+pub fn verify_sha256(path: &Path, expected: &str) -> Result<(), KuboBinaryError> {
+    let metadata = fs::metadata(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => KuboBinaryError::NotFound(path.to_path_buf()),
+        _ => io_error(path, e),
+    })?;
+    let stamp = (
+        path.to_path_buf(),
+        metadata.len(),
+        metadata.modified().ok(),
+        expected.to_ascii_lowercase(),
+    );
+    if VERIFIED.lock().unwrap().as_ref() == Some(&stamp) {
+        return Ok(());
+    }
+
+    let actual = sha256_file(path)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(KuboBinaryError::ChecksumMismatch {
+            path: path.to_path_buf(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    info!("Verified Kubo binary {} (sha256 {})", path.display(), actual);
+    *VERIFIED.lock().unwrap() = Some(stamp);
+    Ok(())
+}
+
+/// 是否为 64 位十六进制的 SHA-256
+pub fn is_sha256_hex(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// 在清单中查找文件对应的哈希（忽略空行和 # 注释）
+pub fn manifest_entry<'a>(content: &'a str, name: &str) -> Result<Option<&'a str>, KuboBinaryError> {
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || KuboBinaryError::InvalidManifest(line.to_string());
+        let (hash, file) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+        if !is_sha256_hex(hash) {
+            return Err(invalid());
+        }
+        // sha256sum 的二进制模式会在文件名前加 '*'
+        let file = file.trim_start();
+        if file.strip_prefix('*').unwrap_or(file) == name {
+            return Ok(Some(hash));
+        }
+    }
+    Ok(None)
+}
+
+fn sha256_file(path: &Path) -> Result<String, KuboBinaryError> {
+    let mut file = File::open(path).map_err(|e| io_error(path, e))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).map_err(|e| io_error(path, e))?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+fn io_error(path: &Path, source: io::Error) -> KuboBinaryError {
+    KuboBinaryError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_against_manifest() {
+        assert_eq!(kubo_binary_name("linux", "aarch64").unwrap(), "kubo-linux-arm64");
+        assert!(matches!(
+            kubo_binary_name("linux", "riscv64"),
+            Err(KuboBinaryError::UnsupportedPlatform { .. })
+        ));
+
+        let dir = std::env::temp_dir().join(format!("planet-kubo-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let binary = dir.join("kubo-linux-arm64");
+        let manifest = dir.join(MANIFEST_FILE);
+        fs::write(&binary, "hello").unwrap();
+
+        // sha2-256("hello")
+        let hash = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        fs::write(&manifest, format!("# kubo v0.32.1\n{}  kubo-linux-amd64\n", "0".repeat(64))).unwrap();
+        assert!(matches!(
+            verify(&binary, &manifest),
+            Err(KuboBinaryError::ManifestEntryMissing(_))
+        ));
+        fs::write(&manifest, format!("{} *kubo-linux-arm64\n", hash)).unwrap();
+        verify(&binary, &manifest).unwrap();
+
+        verify_sha256(&binary, &hash.to_uppercase()).unwrap();
+
+        fs::write(&binary, "tampered").unwrap();
+        assert!(matches!(
+            verify(&binary, &manifest),
+            Err(KuboBinaryError::ChecksumMismatch { .. })
+        ));
+        assert!(matches!(
+            verify_sha256(&binary, hash),
+            Err(KuboBinaryError::ChecksumMismatch { .. })
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
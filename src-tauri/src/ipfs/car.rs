@@ -0,0 +1,204 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+// ============================================================
+// CAR 文件头解析
+// CARv1：varint(头部长度) + DAG-CBOR 头部 { roots: [CID], version: 1 }
+// 只解析头部中的根 CID，用于导入前校验归档内容
+// ============================================================
+
+/// 头部长度上限，防止读入异常文件
+const MAX_HEADER_LEN: u64 = 1024 * 1024;
+
+/// 读取 CAR 文件的根 CID 列表
+pub fn read_roots(path: &Path) -> Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let header_len = read_varint(&mut file)?;
+    if header_len == 0 || header_len > MAX_HEADER_LEN {
+        return Err(anyhow!("Invalid CAR header length: {}", header_len));
+    }
+    let mut header = vec![0u8; header_len as usize];
+    file.read_exact(&mut header)?;
+    parse_header(&header)
+}
+
+fn read_varint(reader: &mut impl Read) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(anyhow!("CAR header length varint is too long"))
+}
+
+/// 解析 DAG-CBOR 编码的 CARv1 头部，返回根 CID 字符串
+pub fn parse_header(data: &[u8]) -> Result<Vec<String>> {
+    let mut cbor = Cbor { data, pos: 0 };
+    let entries = cbor.expect(5, "map")?;
+    let mut roots = None;
+    let mut version = None;
+    for _ in 0..entries {
+        let key_len = cbor.expect(3, "text key")?;
+        match cbor.take(key_len)? {
+            b"roots" => {
+                let count = cbor.expect(4, "roots array")?;
+                let mut cids = Vec::new();
+                for _ in 0..count {
+                    if cbor.expect(6, "CID tag")? != 42 {
+                        return Err(anyhow!("Unexpected CBOR tag in CAR roots"));
+                    }
+                    let len = cbor.expect(2, "CID bytes")?;
+                    // DAG-CBOR 中 CID 字节以 0x00（identity multibase）开头
+                    match cbor.take(len)? {
+                        [0x00, cid @ ..] => cids.push(cid_to_string(cid)?),
+                        _ => return Err(anyhow!("Invalid CID in CAR roots")),
+                    }
+                }
+                roots = Some(cids);
+            }
+            b"version" => version = Some(cbor.expect(0, "version")?),
+            _ => return Err(anyhow!("Unexpected key in CAR header")),
+        }
+    }
+    match (version, roots) {
+        (Some(1), Some(roots)) => Ok(roots),
+        (Some(v), _) if v != 1 => Err(anyhow!("Unsupported CAR version {}", v)),
+        _ => Err(anyhow!("CAR header is missing roots or version")),
+    }
+}
+
+/// 最小的 CBOR 读取器，只支持 CAR 头部用到的类型
+struct Cbor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cbor<'a> {
+    /// 读取一个数据项的头部，要求主类型为 major，返回其参数
+    fn expect(&mut self, major: u8, what: &str) -> Result<u64> {
+        let initial = *self
+            .data
+            .get(self.pos)
+            .ok_or_else(|| anyhow!("CAR header ended while reading {}", what))?;
+        self.pos += 1;
+        if initial >> 5 != major {
+            return Err(anyhow!("Unexpected CBOR type while reading {}", what));
+        }
+        let size = match initial & 0x1f {
+            n @ 0..=23 => return Ok(n as u64),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(anyhow!("Unsupported CBOR length while reading {}", what)),
+        };
+        Ok(self
+            .take(size)?
+            .iter()
+            .fold(0u64, |acc, b| (acc << 8) | *b as u64))
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len as usize)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow!("CAR header is truncated"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+}
+
+/// 将二进制 CID 编码为 Kubo 默认的字符串形式
+/// CIDv0（sha2-256 multihash）使用 base58btc，CIDv1 使用 base32
+pub fn cid_to_string(cid: &[u8]) -> Result<String> {
+    match cid {
+        [0x12, 0x20, digest @ ..] if digest.len() == 32 => Ok(base58btc(cid)),
+        [0x01, ..] => Ok(format!("b{}", base32_lower(cid))),
+        _ => Err(anyhow!("Unsupported CID encoding")),
+    }
+}
+
+fn base32_lower(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+    let mut out = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+fn base58btc(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in data {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = data.iter().take_while(|b| **b == 0).count();
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|d| ALPHABET[*d as usize] as char))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header_reads_roots() {
+        // sha2-256("hello")
+        let hex = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let digest: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        let v1: Vec<u8> = [&[0x01, 0x70, 0x12, 0x20][..], &digest].concat();
+        let v0: Vec<u8> = [&[0x12, 0x20][..], &digest].concat();
+
+        let mut header = vec![0xa2, 0x65];
+        header.extend_from_slice(b"roots");
+        header.push(0x82);
+        for cid in [&v1, &v0] {
+            header.extend_from_slice(&[0xd8, 0x2a, 0x58, cid.len() as u8 + 1, 0x00]);
+            header.extend_from_slice(cid);
+        }
+        header.push(0x67);
+        header.extend_from_slice(b"version");
+        header.push(0x01);
+
+        assert_eq!(
+            parse_header(&header).unwrap(),
+            vec![
+                "bafybeibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq".to_string(),
+                "QmRN6wdp1S2A5EtjW9A3M1vKSBuQQGcgvuhoMUoEz4iiT5".to_string(),
+            ]
+        );
+        assert!(parse_header(&header[..header.len() - 3]).is_err());
+    }
+}
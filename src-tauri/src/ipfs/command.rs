@@ -7,35 +7,58 @@ use tokio::process::Command as TokioCommand;
 use tokio::sync::mpsc;
 use tracing::{debug, info};
 
+use super::binary::{self, KuboBinaryError};
 use crate::helpers::paths;
+use crate::models::settings::KuboNode;
 
 /// Kubo CLI 命令封装
 /// 对应原项目 IPFSCommand.swift
 pub struct KuboCommand {
     app: AppHandle,
     args: Vec<String>,
+    /// 命令的目标节点，默认是本机仓库
+    node: KuboNode,
 }
 
+/// 目录 add 的分块参数，CLI 与 HTTP 的 add 使用同一值，only-hash 算出的 CID 才与实际 add 相同
+pub const ADD_CHUNKER: &str = "size-262144";
+
+/// 只作用于本机仓库的命令，即使指定了外部节点也不会加 --api
+const LOCAL_ONLY_COMMANDS: &[&[&str]] = &[
+    &["init"],
+    &["daemon"],
+    &["shutdown"],
+    &["config"],
+    &["repo", "migrate"],
+];
+
 impl KuboCommand {
     /// 创建新的 KuboCommand
     pub fn new(app: AppHandle) -> Self {
+        Self::with_args(app, Vec::new())
+    }
+
+    /// 带参数创建
+    pub fn with_args(app: AppHandle, args: Vec<String>) -> Self {
         Self {
             app,
-            args: Vec::new(),
+            args,
+            node: KuboNode::Bundled,
         }
     }
 
-    /// 带参数创建
-    pub fn with_args(app: AppHandle, args: Vec<String>) -> Self {
-        Self { app, args }
+    /// 指定目标节点（由 IpfsDaemon 传入 setup 时确定的节点，而不是读取当前设置）
+    pub fn on_node(mut self, node: &KuboNode) -> Self {
+        self.node = node.clone();
+        self
     }
     // ============================================================
     // 路径
     // ============================================================
 
-    /// Kubo 可执行文件路径
-    pub fn executable_path(&self) -> PathBuf {
-        paths::get_kubo_path(&self.app)
+    /// Kubo 可执行文件路径；打包的 Kubo 会先做 SHA-256 校验
+    pub fn executable_path(&self) -> Result<PathBuf, KuboBinaryError> {
+        binary::verified_path(&self.app)
     }
 
     /// IPFS 仓库路径 (~/.planet/ipfs/)
@@ -46,33 +69,63 @@ impl KuboCommand {
         repo
     }
 
+    fn is_local_only(&self) -> bool {
+        LOCAL_ONLY_COMMANDS.iter().any(|prefix| {
+            self.args
+                .iter()
+                .map(String::as_str)
+                .take(prefix.len())
+                .eq(prefix.iter().copied())
+        })
+    }
+
+    /// 实际传给 Kubo 的参数
+    /// 目标为外部节点时加上 --api，命令由该节点执行；生命周期与配置命令始终作用于本机仓库
+    /// 认证信息不能放进命令行（会出现在进程列表中），需要认证的外部节点只能走 HTTP API
+    fn command_args(&self) -> Result<Vec<String>> {
+        let mut args = Vec::new();
+        if let KuboNode::External { api, auth, .. } = &self.node {
+            if !self.is_local_only() {
+                if auth.is_some() {
+                    return Err(anyhow!(
+                        "The Kubo CLI cannot authenticate to {}; use the HTTP API instead",
+                        api
+                    ));
+                }
+                args.push(format!("--api={}", api));
+            }
+        }
+        args.extend(self.args.iter().cloned());
+        Ok(args)
+    }
+
+    /// 子进程的 PATH：打包的迁移程序目录优先
+    pub fn search_path(&self) -> std::ffi::OsString {
+        let mut dirs = vec![paths::get_migrations_path(&self.app)];
+        if let Some(path) = std::env::var_os("PATH") {
+            dirs.extend(std::env::split_paths(&path));
+        }
+        std::env::join_paths(dirs).unwrap_or_default()
+    }
+
     // ============================================================
     // 执行方法
     // ============================================================
 
+    /// 在阻塞线程池中执行 run()，避免阻塞 async 运行时
+    pub async fn run_async(self) -> Result<CmdOutput> {
+        tokio::task::spawn_blocking(move || self.run()).await?
+    }
+
+    /// 在阻塞线程池中执行 run_raw()
+    pub async fn run_raw_async(self) -> Result<Output> {
+        tokio::task::spawn_blocking(move || self.run_raw()).await?
+    }
+
     /// 同步执行 Kubo 命令，等待完成并返回结果
     /// 对应 Swift: IPFSCommand.run() -> (ret, out, err)
     pub fn run(&self) -> Result<CmdOutput> {
-        let exe = self.executable_path();
-        let repo = self.repo_path();
-
-        // 增强日志输出
-        info!("=== Executing Kubo Command ===");
-        info!("Executable: {:?}", exe);
-        info!("Arguments: {:?}", self.args);
-        info!("IPFS_PATH: {:?}", repo);
-        info!("File exists: {}", exe.exists());
-
-        debug!("Running kubo: {:?} {:?}", exe, self.args);
-
-        let output: Output = Command::new(&exe)
-            .args(&self.args)
-            .env("IPFS_PATH", repo.to_str().unwrap_or(""))
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .map_err(|e| anyhow!("Failed to execute kubo command: {}", e))?;
-
+        let output = self.run_raw()?;
         let ret = output.status.code().unwrap_or(-1);
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -91,6 +144,30 @@ impl KuboCommand {
         })
     }
 
+    /// 同步执行 Kubo 命令，返回未解码的原始输出（cat 等可能输出二进制内容）
+    pub fn run_raw(&self) -> Result<Output> {
+        let exe = self.executable_path()?;
+        let repo = self.repo_path();
+
+        // 增强日志输出
+        info!("=== Executing Kubo Command ===");
+        info!("Executable: {:?}", exe);
+        info!("Arguments: {:?}", self.args);
+        info!("IPFS_PATH: {:?}", repo);
+        info!("File exists: {}", exe.exists());
+
+        debug!("Running kubo: {:?} {:?}", exe, self.args);
+
+        Command::new(&exe)
+            .args(self.command_args()?)
+            .env("IPFS_PATH", repo.to_str().unwrap_or(""))
+            .env("PATH", self.search_path())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|e| anyhow!("Failed to execute kubo command: {}", e))
+    }
+
     /// 异步流式执行 Kubo 命令（用于 daemon）
     /// 对应 Swift: IPFSCommand.run(outHandler:errHandler:completionHandler:)
     ///
@@ -98,7 +175,7 @@ impl KuboCommand {
     pub async fn run_streaming(
         &self,
     ) -> Result<(tokio::process::Child, mpsc::Receiver<StreamLine>)> {
-        let exe = self.executable_path();
+        let exe = binary::verified_path_async(&self.app).await?;
         let repo = self.repo_path();
         // 增强日志输出
         info!("=== Launching Kubo Daemon (Streaming) ===");
@@ -153,36 +230,51 @@ impl KuboCommand {
 
     /// ipfs init
     pub fn ipfs_init(app: AppHandle) -> Self {
-        Self { app, args: vec!["init".into()] }
+        Self::with_args(app, vec!["init".into()])
     }
 
     /// ipfs version
     pub fn ipfs_version(app: AppHandle) -> Self {
-        Self { app, args: vec!["version".into()] }
+        Self::with_args(app, vec!["version".into()])
+    }
+
+    /// ipfs version --enc=json
+    pub fn ipfs_version_json(app: AppHandle) -> Self {
+        Self::with_args(app, vec!["version".into(), "--enc=json".into()])
+    }
+
+    /// ipfs repo migrate
+    pub fn repo_migrate(app: AppHandle) -> Self {
+        Self::with_args(app, vec!["repo".into(), "migrate".into()])
+    }
+
+    /// ipfs repo stat --enc=json
+    pub fn repo_stat(app: AppHandle) -> Self {
+        Self::with_args(app, vec!["repo".into(), "stat".into(), "--enc=json".into()])
     }
 
     /// ipfs config Addresses.API /ip4/127.0.0.1/tcp/{port}
     pub fn update_api_port(app: AppHandle, port: u16) -> Self {
-        Self {
+        Self::with_args(
             app,
-            args: vec![
+            vec![
                 "config".into(),
                 "Addresses.API".into(),
                 format!("/ip4/127.0.0.1/tcp/{}", port),
             ],
-        }
+        )
     }
 
     /// ipfs config Addresses.Gateway /ip4/127.0.0.1/tcp/{port}
     pub fn update_gateway_port(app: AppHandle, port: u16) -> Self {
-        Self {
+        Self::with_args(
             app,
-            args: vec![
+            vec![
                 "config".into(),
                 "Addresses.Gateway".into(),
                 format!("/ip4/127.0.0.1/tcp/{}", port),
             ],
-        }
+        )
     }
 
     /// ipfs config Addresses.Swarm [多地址] --json
@@ -190,168 +282,128 @@ impl KuboCommand {
         let swarm_json = format!(
             r#"["/ip4/0.0.0.0/tcp/{port}", "/ip6/::/tcp/{port}", "/ip4/0.0.0.0/udp/{port}/quic", "/ip6/::/udp/{port}/quic"]"#,
         );
-        Self {
+        Self::with_args(
             app,
-            args: vec![
+            vec![
                 "config".into(),
                 "Addresses.Swarm".into(),
                 swarm_json,
                 "--json".into(),
             ],
-        }
+        )
     }
 
-    /// ipfs config Peering.Peers {json} --json
-    pub fn set_peers(app: AppHandle, peers_json: &str) -> Self {
-        Self {
+    /// ipfs config Datastore.StorageMax {value}
+    pub fn set_storage_max(app: AppHandle, value: &str) -> Self {
+        Self::with_args(
             app,
-            args: vec![
+            vec![
                 "config".into(),
-                "Peering.Peers".into(),
-                peers_json.into(),
-                "--json".into(),
+                "Datastore.StorageMax".into(),
+                value.into(),
             ],
-        }
+        )
     }
 
-    /// ipfs config DNS.Resolvers {json} --json
-    pub fn set_resolvers(app: AppHandle, resolvers_json: &str) -> Self {
-        Self {
-            app,
-            args: vec![
-                "config".into(),
-                "DNS.Resolvers".into(),
-                resolvers_json.into(),
-                "--json".into(),
-            ],
-        }
+    /// ipfs config show（不包含私钥）
+    pub fn config_show(app: AppHandle) -> Self {
+        Self::with_args(app, vec!["config".into(), "show".into()])
     }
 
-    /// ipfs config Swarm.ConnMgr {json} --json
-    pub fn set_swarm_conn_mgr(app: AppHandle, json: &str) -> Self {
-        Self {
-            app,
-            args: vec![
-                "config".into(),
-                "Swarm.ConnMgr".into(),
-                json.into(),
-                "--json".into(),
-            ],
-        }
-    }
-
-    /// ipfs config API.HTTPHeaders.Access-Control-Allow-Origin {json} --json
-    pub fn set_access_control_allow_origin(app: AppHandle, json: &str) -> Self {
-        Self {
-            app,
-            args: vec![
-                "config".into(),
-                "API.HTTPHeaders.Access-Control-Allow-Origin".into(),
-                json.into(),
-                "--json".into(),
-            ],
-        }
-    }
-
-    /// ipfs config API.HTTPHeaders.Access-Control-Allow-Methods {json} --json
-    pub fn set_access_control_allow_methods(app: AppHandle, json: &str) -> Self {
-        Self {
-            app,
-            args: vec![
-                "config".into(),
-                "API.HTTPHeaders.Access-Control-Allow-Methods".into(),
-                json.into(),
-                "--json".into(),
-            ],
-        }
+    /// ipfs config replace {file}（Kubo 保留原有私钥）
+    pub fn config_replace(app: AppHandle, file: &str) -> Self {
+        Self::with_args(app, vec!["config".into(), "replace".into(), file.into()])
     }
 
     /// ipfs daemon --migrate --enable-namesys-pubsub --enable-pubsub-experiment
     pub fn launch_daemon(app: AppHandle) -> Self {
-        Self {
+        Self::with_args(
             app,
-            args: vec![
+            vec![
                 "daemon".into(),
                 "--migrate".into(),
                 "--enable-namesys-pubsub".into(),
                 "--enable-pubsub-experiment".into(),
             ],
-        }
+        )
     }
 
     /// ipfs shutdown
     pub fn shutdown_daemon(app: AppHandle) -> Self {
-        Self {
-            app,
-            args: vec!["shutdown".into()],
-        }
+        Self::with_args(app, vec!["shutdown".into()])
     }
 
-    /// ipfs add -r -H {directory} --cid-version=1 --quieter
+    /// ipfs add -r -H {directory} --cid-version=1 --chunker={ADD_CHUNKER} --quieter
     pub fn add_directory(app: AppHandle, directory: &str) -> Self {
-        Self {
-            app,
-            args: vec![
-                "add".into(),
-                "-r".into(),
-                "-H".into(),
-                directory.into(),
-                "--cid-version=1".into(),
-                "--quieter".into(),
-            ],
-        }
+        Self::with_args(app, Self::add_directory_args(directory))
+    }
+
+    /// ipfs add -r -H {directory} --cid-version=1 --chunker={ADD_CHUNKER} --quieter --only-hash
+    /// 参数与 add_directory 保持一致，保证算出的 CID 与实际添加后相同
+    pub fn get_directory_cid(app: AppHandle, directory: &str) -> Self {
+        let mut args = Self::add_directory_args(directory);
+        args.push("--only-hash".into());
+        Self::with_args(app, args)
+    }
+
+    fn add_directory_args(directory: &str) -> Vec<String> {
+        vec![
+            "add".into(),
+            "-r".into(),
+            "-H".into(),
+            directory.into(),
+            "--cid-version=1".into(),
+            format!("--chunker={}", ADD_CHUNKER),
+            "--quieter".into(),
+        ]
     }
 
     /// ipfs add {file} --quieter --cid-version=1 --only-hash
     pub fn get_file_cid(app: AppHandle, file: &str) -> Self {
-        Self {
+        Self::with_args(
             app,
-            args: vec![
+            vec![
                 "add".into(),
                 file.into(),
                 "--quieter".into(),
                 "--cid-version=1".into(),
                 "--only-hash".into(),
             ],
-        }
+        )
     }
 
     /// ipfs add {file} --quieter --cid-version=0 --pin
     pub fn get_file_cid_v0(app: AppHandle, file: &str) -> Self {
-        Self {
+        Self::with_args(
             app,
-            args: vec![
+            vec![
                 "add".into(),
                 file.into(),
                 "--quieter".into(),
                 "--cid-version=0".into(),
                 "--pin".into(),
             ],
-        }
+        )
     }
 
     /// ipfs key gen {name}
     pub fn generate_key(app: AppHandle, name: &str) -> Self {
-        Self {
-            app,
-            args: vec!["key".into(), "gen".into(), name.into()],
-        }
+        Self::with_args(app, vec!["key".into(), "gen".into(), name.into()])
     }
 
     /// ipfs key rm {name}
     pub fn delete_key(app: AppHandle, name: &str) -> Self {
-        Self {
-            app,
-            args: vec!["key".into(), "rm".into(), name.into()],
-        }
+        Self::with_args(app, vec!["key".into(), "rm".into(), name.into()])
     }
 
     /// ipfs key list
     pub fn list_keys(app: AppHandle) -> Self {
-        Self {
-            app,
-            args: vec!["key".into(), "list".into()],
-        }
+        Self::with_args(app, vec!["key".into(), "list".into()])
+    }
+
+    /// ipfs key list -l（输出 "{key_id} {name}"）
+    pub fn list_keys_with_ids(app: AppHandle) -> Self {
+        Self::with_args(app, vec!["key".into(), "list".into(), "-l".into()])
     }
 
     /// ipfs key export {name} -o {target} [--format={format}]
@@ -366,7 +418,7 @@ impl KuboCommand {
         if let Some(fmt) = format {
             args.push(format!("--format={}", fmt));
         }
-        Self { app, args }
+        Self::with_args(app, args)
     }
 
     /// ipfs key import {name} {target} [--format={format}]
@@ -380,7 +432,7 @@ impl KuboCommand {
         if let Some(fmt) = format {
             args.push(format!("--format={}", fmt));
         }
-        Self { app, args }
+        Self::with_args(app, args)
     }
 }
 
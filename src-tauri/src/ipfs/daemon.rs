@@ -1,15 +1,33 @@
 use anyhow::{anyhow, Result};
 use reqwest::Client;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::path::Path;
+use std::process::ExitStatus;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter};
+use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
+use super::backend::{AddProgressFn, IpfsBackend, IpfsKey, KuboCliBackend, KuboHttpBackend};
+use super::binary;
 use super::command::{KuboCommand, StreamLine};
+use super::logs::{DaemonLogs, DaemonLogsHandle, LogStream, DEFAULT_LOG_CAPACITY};
+use super::migration;
 use super::models::*;
-use crate::helpers::net;
+use super::pins::{parse_pin_progress, PinProgress};
+use super::profile;
+use super::repo;
+use crate::models::settings::{AppSettings, KuboNode};
+
+/// 保留的 daemon stderr 末尾行数（用于崩溃诊断）
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Kubo 的 Datastore.StorageMax 默认值
+const KUBO_DEFAULT_STORAGE_MAX: &str = "10GB";
 
 /// IPFS Daemon 管理器
 /// 对应原项目 IPFSDaemon.swift (actor)
@@ -22,6 +40,12 @@ pub struct IpfsDaemon {
     http_client: Client,
     /// daemon 子进程 handle（用于 shutdown 时 kill）
     daemon_child: Option<tokio::process::Child>,
+    /// daemon 输出日志缓冲
+    logs: DaemonLogsHandle,
+    /// 最近一次启动 daemon 的时间
+    launched_at: Option<DateTime<Utc>>,
+    /// 使用的节点，每次 setup 时从设置中读取
+    node: KuboNode,
 }
 
 impl IpfsDaemon {
@@ -38,6 +62,22 @@ impl IpfsDaemon {
                 .build()
                 .expect("Failed to create HTTP client"),
             daemon_child: None,
+            logs: Arc::new(DaemonLogs::new(DEFAULT_LOG_CAPACITY)),
+            launched_at: None,
+            node: KuboNode::default(),
+        }
+    }
+
+    /// 是否由应用管理 daemon；外部节点不 setup / launch / shutdown
+    pub fn is_managed(&self) -> bool {
+        self.node.is_managed()
+    }
+
+    /// 外部节点配置的网关地址
+    pub fn external_gateway(&self) -> Option<String> {
+        match &self.node {
+            KuboNode::External { gateway, .. } => gateway.clone(),
+            KuboNode::Bundled => None,
         }
     }
 
@@ -53,7 +93,18 @@ impl IpfsDaemon {
         }
         self.setting_up = true;
 
-        let result = self.do_setup().await;
+        self.node = AppSettings::current(&self.app).kubo_node;
+        let result = match &self.node {
+            KuboNode::Bundled => match binary::verified_path_async(&self.app).await {
+                // 先在阻塞线程池中校验 Kubo，之后 setup 中的同步命令直接命中校验缓存
+                Ok(_) => self.do_setup().await,
+                Err(e) => Err(e),
+            },
+            KuboNode::External { api, .. } => {
+                info!("Using external Kubo node at {}, skipping setup", api);
+                self.http().map(|_| ())
+            }
+        };
 
         self.setting_up = false;
         result
@@ -81,12 +132,21 @@ impl IpfsDaemon {
                 return Err(anyhow!("Failed to init IPFS repo"));
             }
             info!("IPFS repo initialized");
+        } else {
+            // 已有仓库：清理崩溃遗留的锁文件（自己的 daemon 仍在运行时跳过）
+            if self.daemon_child.is_none() {
+                repo::ensure_unlocked(&self.app, &repo_path).await?;
+            }
+            repo::check_config(&repo_path)?;
+            // 版本落后于内置 Kubo 时先迁移，否则后续 config 命令和 daemon 都会失败
+            migration::ensure_repo_version(&self.app, &repo_path).await?;
         }
 
-        // 2. 扫描并配置 Swarm 端口 (4001-4011)
-        info!("Scanning swarm port...");
-        let swarm_port = net::scout_port(4001..=4011)
-            .ok_or_else(|| anyhow!("Unable to find open swarm port"))?;
+        // 2-4. 选择端口：优先复用设置中保存的端口，冲突时才重新扫描
+        //      每次都写入 Kubo 配置，保证 daemon 监听的端口与 api_port 一致
+        let settings = AppSettings::current(&self.app);
+
+        let swarm_port = settings.swarm_port.choose("swarm")?;
         let output = KuboCommand::update_swarm_port(self.app.clone(), swarm_port).run()?;
         if output.ret != 0 {
             return Err(anyhow!("Failed to update swarm port: {}", output.stderr));
@@ -94,10 +154,7 @@ impl IpfsDaemon {
         self.swarm_port = Some(swarm_port);
         info!("Swarm port: {}", swarm_port);
 
-        // 3. 扫描并配置 API 端口 (5981-5991)
-        info!("Scanning API port...");
-        let api_port = net::scout_port(5981..=5991)
-            .ok_or_else(|| anyhow!("Unable to find open API port"))?;
+        let api_port = settings.api_port.choose("API")?;
         let output = KuboCommand::update_api_port(self.app.clone(), api_port).run()?;
         if output.ret != 0 {
             return Err(anyhow!("Failed to update API port: {}", output.stderr));
@@ -105,10 +162,7 @@ impl IpfsDaemon {
         self.api_port = Some(api_port);
         info!("API port: {}", api_port);
 
-        // 4. 扫描并配置 Gateway 端口 (18181-18191)
-        info!("Scanning gateway port...");
-        let gateway_port = net::scout_port(18181..=18191)
-            .ok_or_else(|| anyhow!("Unable to find open gateway port"))?;
+        let gateway_port = settings.gateway_port.choose("gateway")?;
         let output = KuboCommand::update_gateway_port(self.app.clone(), gateway_port).run()?;
         if output.ret != 0 {
             return Err(anyhow!(
@@ -119,45 +173,30 @@ impl IpfsDaemon {
         self.gateway_port = Some(gateway_port);
         info!("Gateway port: {}", gateway_port);
 
-        // 5. 配置 Peering Peers
-        info!("Setting peering peers...");
-        let peers_json = Self::peers_json();
-        let output = KuboCommand::set_peers(self.app.clone(), &peers_json).run()?;
-        if output.ret != 0 {
-            warn!("Failed to set peers: {}", output.stderr);
-        }
-
-        // 6. 配置 DNS Resolvers
-        info!("Setting DNS resolvers...");
-        let resolvers_json = Self::resolvers_json();
-        let output = KuboCommand::set_resolvers(self.app.clone(), &resolvers_json).run()?;
-        if output.ret != 0 {
-            warn!("Failed to set DNS resolvers: {}", output.stderr);
+        if let Err(e) = AppSettings::update(&self.app, |s| {
+            s.swarm_port.last_used = Some(swarm_port);
+            s.api_port.last_used = Some(api_port);
+            s.gateway_port.last_used = Some(gateway_port);
+        }) {
+            warn!("Failed to save daemon ports: {}", e);
         }
 
-        // 7. 配置 Swarm Connection Manager (low: 10-20)
-        info!("Setting Swarm Connection Manager...");
-        let conn_mgr_json = serde_json::json!({
-            "GracePeriod": "20s",
-            "HighWater": 20,
-            "LowWater": 10,
-            "Type": "basic"
-        })
-        .to_string();
-        let output = KuboCommand::set_swarm_conn_mgr(self.app.clone(), &conn_mgr_json).run()?;
+        // 仓库容量上限；未设置时写回 Kubo 默认值，清除之前写入的上限
+        let value = settings
+            .storage_max_gb
+            .map(|gb| format!("{}GB", gb))
+            .unwrap_or_else(|| KUBO_DEFAULT_STORAGE_MAX.to_string());
+        let output = KuboCommand::set_storage_max(self.app.clone(), &value).run()?;
         if output.ret != 0 {
-            return Err(anyhow!(
-                "Failed to set SwarmConnMgr: {}",
-                output.stderr
-            ));
+            warn!("Failed to set Datastore.StorageMax: {}", output.stderr);
+        } else {
+            info!("Datastore.StorageMax: {}", value);
         }
 
-        // 8. 配置 CORS
-        info!("Setting Access-Control headers...");
-        let allow_origin = serde_json::json!(["https://webui.ipfs.io"]).to_string();
-        let _ = KuboCommand::set_access_control_allow_origin(self.app.clone(), &allow_origin).run();
-        let allow_methods = serde_json::json!(["PUT", "POST"]).to_string();
-        let _ = KuboCommand::set_access_control_allow_methods(self.app.clone(), &allow_methods).run();
+        // 5. 网络配置方案：ConnMgr、Peering、DoH、Reprovider、DHT、中继与 CORS，一次写入
+        let profile = settings.active_network_profile()?;
+        info!("Applying network profile {}...", profile.name);
+        profile::apply(&self.app, profile)?;
 
         info!("IPFS setup completed!");
         Ok(())
@@ -170,6 +209,15 @@ impl IpfsDaemon {
     /// 启动 IPFS daemon 子进程
     /// 监听 stdout 中的 "Daemon is ready" 来确认启动成功
     pub async fn launch(&mut self) -> Result<()> {
+        self.node = AppSettings::current(&self.app).kubo_node;
+        if let KuboNode::External { api, .. } = &self.node {
+            return if self.check_online().await {
+                info!("Connected to external Kubo node at {}", api);
+                Ok(())
+            } else {
+                Err(anyhow!("Cannot reach the Kubo API at {}", api))
+            };
+        }
         info!("Launching IPFS daemon...");
 
         // 端口必须已配置
@@ -190,11 +238,14 @@ impl IpfsDaemon {
         let api_port = self.api_port.unwrap();
         let ready = Arc::new(Mutex::new(false));
         let ready_clone = ready.clone();
+        let logs = self.logs.clone();
+        let app = self.app.clone();
+        self.launched_at = Some(Utc::now());
 
         // 在后台任务中持续读取输出
         tokio::spawn(async move {
             while let Some(line) = rx.recv().await {
-                match &line {
+                let (stream, text) = match line {
                     StreamLine::Stdout(text) => {
                         debug!("[IPFS stdout] {}", text);
                         if text.contains("Daemon is ready") {
@@ -202,11 +253,15 @@ impl IpfsDaemon {
                             let mut r = ready_clone.lock().await;
                             *r = true;
                         }
+                        (LogStream::Stdout, text)
                     }
                     StreamLine::Stderr(text) => {
                         debug!("[IPFS stderr] {}", text);
+                        (LogStream::Stderr, text)
                     }
-                }
+                };
+                let entry = logs.push(stream, text);
+                let _ = app.emit("ipfs:log", &entry);
             }
             info!("IPFS daemon process output stream ended");
         });
@@ -237,6 +292,11 @@ impl IpfsDaemon {
 
     /// 优雅关闭 IPFS daemon
     pub async fn shutdown(&mut self) -> Result<()> {
+        if !self.is_managed() {
+            return Err(anyhow!(
+                "The IPFS node is not managed by Planet, shutdown is disabled"
+            ));
+        }
         info!("Shutting down IPFS daemon...");
 
         // 方法1: 通过 CLI 发送 shutdown 命令
@@ -260,6 +320,33 @@ impl IpfsDaemon {
         Ok(())
     }
 
+    /// daemon 子进程是否已退出；已退出时返回退出状态并释放 handle
+    pub fn try_wait_exit(&mut self) -> Option<ExitStatus> {
+        let child = self.daemon_child.as_mut()?;
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                self.daemon_child = None;
+                Some(status)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to check daemon process: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 本次启动以来 daemon 最近的 stderr 输出
+    pub fn stderr_tail(&self) -> Vec<String> {
+        self.logs
+            .tail(LogStream::Stderr, self.launched_at, STDERR_TAIL_LINES)
+    }
+
+    /// daemon 输出日志缓冲（不经过 IpfsState 的锁即可读取）
+    pub fn logs(&self) -> DaemonLogsHandle {
+        self.logs.clone()
+    }
+
     // ============================================================
     // HTTP API 调用 — 对应 Swift: api(path:args:timeout:)
     // ============================================================
@@ -271,41 +358,35 @@ impl IpfsDaemon {
         args: Option<&HashMap<String, String>>,
         timeout_secs: Option<u64>,
     ) -> Result<Vec<u8>> {
-        let api_port = self
-            .api_port
-            .ok_or_else(|| anyhow!("IPFS API port not set"))?;
-
-        let mut url = format!("http://127.0.0.1:{}/api/v0/{}", api_port, path);
-
-        // 添加查询参数
-        if let Some(params) = args {
-            let query: String = params
-                .iter()
-                .map(|(k, v)| format!("{}={}", k, v))
-                .collect::<Vec<_>>()
-                .join("&");
-            url = format!("{}?{}", url, query);
-        }
-
-        let client = if let Some(t) = timeout_secs {
-            Client::builder()
-                .timeout(Duration::from_secs(t))
-                .build()?
-        } else {
-            self.http_client.clone()
-        };
+        let response = self.api_request(path, args, timeout_secs).await?;
+        let data = response.bytes().await?.to_vec();
+        Ok(data)
+    }
 
-        let response = client.post(&url).send().await?;
+    /// 发送 API 请求并检查状态码，返回未读取的响应（用于流式输出）
+    async fn api_request(
+        &self,
+        path: &str,
+        args: Option<&HashMap<String, String>>,
+        timeout_secs: Option<u64>,
+    ) -> Result<reqwest::Response> {
+        self.http()?.request(path, args, timeout_secs).await
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            error!("IPFS API error: {} {} - {}", path, status, body);
-            return Err(anyhow!("IPFS API error: {} {}", path, status));
+    /// daemon 的 HTTP RPC 后端（本机 daemon 或外部节点）
+    pub fn http(&self) -> Result<KuboHttpBackend> {
+        if let KuboNode::External { api, auth, .. } = &self.node {
+            return KuboHttpBackend::external(api, auth.clone(), self.http_client.clone());
         }
+        let api_port = self
+            .api_port
+            .ok_or_else(|| anyhow!("IPFS API port not set"))?;
+        Ok(KuboHttpBackend::local(api_port, self.http_client.clone()))
+    }
 
-        let data = response.bytes().await?.to_vec();
-        Ok(data)
+    /// Kubo CLI 后端
+    pub fn cli(&self) -> KuboCliBackend {
+        KuboCliBackend::new(self.app.clone(), self.node.clone())
     }
 
     /// 便捷方法：调用 API 并反序列化为 JSON
@@ -325,24 +406,86 @@ impl IpfsDaemon {
 
     /// 检查 daemon 是否在线
     pub async fn check_online(&self) -> bool {
-        let Some(api_port) = self.api_port else {
+        let Ok(http) = self.http() else {
             return false;
         };
-        let url = format!("http://127.0.0.1:{}/api/v0/id", api_port);
-        let client = Client::builder()
-            .timeout(Duration::from_secs(5))
-            .build()
-            .unwrap();
-        match client.post(&url).send().await {
-            Ok(resp) => resp.status().is_success(),
-            Err(_) => false,
-        }
+        http.request("id", None, Some(5)).await.is_ok()
     }
 
-    /// 获取带宽统计
-    /// 对应 Swift: getStatsBW()
-    pub async fn get_stats_bw(&self) -> Result<IpfsBandwidth> {
-        self.api_json("stats/bw", None).await
+    /// 获取当前连接的 peer 数量
+    pub async fn get_peer_count(&self) -> Result<usize> {
+        let peers: IpfsPeers = self.api_json("swarm/peers", None).await?;
+        Ok(peers.peers.map_or(0, |p| p.len()))
+    }
+
+    // ============================================================
+    // Swarm
+    // ============================================================
+
+    /// 列出已连接的 peer，包含地址、延迟和方向
+    pub async fn swarm_peers_verbose(&self) -> Result<Vec<IpfsPeer>> {
+        let mut args = HashMap::new();
+        args.insert("verbose".into(), "true".into());
+        args.insert("latency".into(), "true".into());
+        args.insert("direction".into(), "true".into());
+        let peers: IpfsPeers = self.api_json("swarm/peers", Some(&args)).await?;
+        Ok(peers.peers.unwrap_or_default())
+    }
+
+    /// 连接到指定 multiaddr（需包含 /p2p/{peer id}）
+    pub async fn swarm_connect(&self, addr: &str) -> Result<Vec<String>> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), addr.into());
+        let data = self.api("swarm/connect", Some(&args), Some(60)).await?;
+        let result: IpfsSwarmResult = serde_json::from_slice(&data)?;
+        info!("Connected to {}", addr);
+        Ok(result.strings)
+    }
+
+    /// 断开指定 multiaddr 的连接
+    pub async fn swarm_disconnect(&self, addr: &str) -> Result<Vec<String>> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), addr.into());
+        let data = self.api("swarm/disconnect", Some(&args), Some(30)).await?;
+        let result: IpfsSwarmResult = serde_json::from_slice(&data)?;
+        info!("Disconnected from {}", addr);
+        Ok(result.strings)
+    }
+
+    /// 当前 Peering.Peers 配置及连接状态
+    /// 读取 daemon 配置失败时退回当前网络方案中的 Peering 节点
+    pub async fn peering_peers(&self) -> Result<Vec<IpfsPeeringPeer>> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), "Peering.Peers".into());
+        let configured: Option<Vec<IpfsPeeringPeer>> = self
+            .api_json::<serde_json::Value>("config", Some(&args))
+            .await
+            .ok()
+            .and_then(|v| serde_json::from_value(v.get("Value")?.clone()).ok());
+        let mut peering = match configured {
+            Some(peers) => peers,
+            None => AppSettings::current(&self.app)
+                .active_network_profile()?
+                .peering_peers
+                .iter()
+                .map(|p| IpfsPeeringPeer {
+                    id: p.id.clone(),
+                    addrs: p.addrs.clone(),
+                    connected: false,
+                })
+                .collect(),
+        };
+
+        let connected: Vec<String> = self
+            .swarm_peers_verbose()
+            .await?
+            .into_iter()
+            .filter_map(|p| p.peer)
+            .collect();
+        for peer in peering.iter_mut() {
+            peer.connected = connected.contains(&peer.id);
+        }
+        Ok(peering)
     }
 
     /// 获取 Server Info（聚合 id + version + swarm/peers）
@@ -356,12 +499,7 @@ impl IpfsDaemon {
         });
 
         // 获取 IPFS 版本
-        let version_info: IpfsVersion =
-            self.api_json("version", None).await.unwrap_or(IpfsVersion {
-                version: String::new(),
-                repo: String::new(),
-                system: String::new(),
-            });
+        let version_info: IpfsVersion = self.get_version().await.unwrap_or_default();
 
         // 获取 peers 数量
         let peers_info: IpfsPeers = self.api_json("swarm/peers", None).await.unwrap_or(IpfsPeers {
@@ -383,6 +521,46 @@ impl IpfsDaemon {
         })
     }
 
+    /// 获取 Kubo 版本
+    pub async fn get_version(&self) -> Result<IpfsVersion> {
+        self.api_json("version", None).await
+    }
+
+    /// 仓库与版本信息：在线时走 HTTP API，离线时调用 Kubo CLI
+    pub async fn repo_stat(&self, online: bool) -> Result<IpfsRepoStat> {
+        if online {
+            return Ok(IpfsRepoStat {
+                online,
+                repo: self.http()?.repo_state().await?,
+                version: self.get_version().await?,
+            });
+        }
+
+        let output = KuboCommand::repo_stat(self.app.clone())
+            .on_node(&self.node)
+            .run_async()
+            .await?;
+        if output.ret != 0 {
+            return Err(anyhow!("Failed to read repo stat: {}", output.stderr.trim()));
+        }
+        let repo: IpfsRepoState = serde_json::from_str(&output.stdout)?;
+
+        let output = KuboCommand::ipfs_version_json(self.app.clone())
+            .on_node(&self.node)
+            .run_async()
+            .await?;
+        if output.ret != 0 {
+            return Err(anyhow!("Failed to read Kubo version: {}", output.stderr.trim()));
+        }
+        let version: IpfsVersion = serde_json::from_str(&output.stdout)?;
+
+        Ok(IpfsRepoStat {
+            online,
+            repo,
+            version,
+        })
+    }
+
     /// 获取仓库大小
     pub async fn get_repo_size(&self) -> Result<i64> {
         let repo_state: IpfsRepoState = self.api_json("repo/stat", None).await?;
@@ -395,41 +573,6 @@ impl IpfsDaemon {
             .map(|p| format!("http://127.0.0.1:{}", p))
     }
 
-    // ============================================================
-    // IPNS / Resolve
-    // ============================================================
-
-    /// 解析 IPNS 或 DNSLink
-    /// 对应 Swift: resolveIPNSorDNSLink(name:)
-    pub async fn resolve_ipns(&self, name: &str) -> Result<String> {
-        let mut args = HashMap::new();
-        args.insert("arg".into(), name.into());
-        let resolved: IpfsResolved = self.api_json("name/resolve", Some(&args)).await?;
-        if resolved.path.starts_with("/ipfs/") {
-            Ok(resolved.path["/ipfs/".len()..].to_string())
-        } else {
-            Err(anyhow!("Unexpected resolve result: {}", resolved.path))
-        }
-    }
-
-    // ============================================================
-    // Pin
-    // ============================================================
-
-    pub async fn pin(&self, cid: &str) -> Result<()> {
-        let mut args = HashMap::new();
-        args.insert("arg".into(), cid.into());
-        self.api("pin/add", Some(&args), Some(120)).await?;
-        Ok(())
-    }
-
-    pub async fn unpin(&self, cid: &str) -> Result<()> {
-        let mut args = HashMap::new();
-        args.insert("arg".into(), cid.into());
-        self.api("pin/rm", Some(&args), Some(120)).await?;
-        Ok(())
-    }
-
     pub async fn gc(&self) -> Result<usize> {
         let data = self.api("repo/gc", None, Some(120)).await?;
         let text = String::from_utf8_lossy(&data);
@@ -442,60 +585,11 @@ impl IpfsDaemon {
     // Key 管理
     // ============================================================
 
-    /// 生成 IPFS 密钥对
-    pub fn generate_key(&self, name: &str) -> Result<String> {
-        let output = KuboCommand::generate_key(self.app.clone(), name).run()?;
-        if output.ret == 0 {
-            Ok(output.stdout.trim().to_string())
-        } else {
-            Err(anyhow!(
-                "Failed to generate key: {}",
-                output.stderr
-            ))
-        }
-    }
-
-    /// 删除 IPFS 密钥
-    pub fn remove_key(&self, name: &str) -> Result<()> {
-        let output = KuboCommand::delete_key(self.app.clone(), name).run()?;
-        if output.ret == 0 {
-            Ok(())
-        } else {
-            Err(anyhow!("Failed to delete key: {}", output.stderr))
-        }
-    }
-
-    /// 列出所有密钥（排除 "self"）
-    pub fn list_keys(&self) -> Result<Vec<String>> {
-        let output = KuboCommand::list_keys(self.app.clone()).run()?;
-        if output.ret == 0 {
-            let keys: Vec<String> = output
-                .stdout
-                .trim()
-                .lines()
-                .filter(|l| !l.is_empty() && *l != "self")
-                .map(|l| l.to_string())
-                .collect();
-            Ok(keys)
-        } else {
-            Err(anyhow!("Failed to list keys: {}", output.stderr))
-        }
-    }
-
-    /// 检查密钥是否存在
-    pub fn check_key_exists(&self, name: &str) -> Result<bool> {
-        let output = KuboCommand::list_keys(self.app.clone()).run()?;
-        if output.ret == 0 {
-            let keys: Vec<&str> = output.stdout.trim().lines().collect();
-            Ok(keys.contains(&name))
-        } else {
-            Ok(false)
-        }
-    }
-
     /// 导出密钥
     pub fn export_key(&self, name: &str, target: &str, format: Option<&str>) -> Result<()> {
-        let output = KuboCommand::export_key(self.app.clone(), name, target, format).run()?;
+        let output = KuboCommand::export_key(self.app.clone(), name, target, format)
+            .on_node(&self.node)
+            .run()?;
         if output.ret == 0 {
             Ok(())
         } else {
@@ -505,7 +599,9 @@ impl IpfsDaemon {
 
     /// 导入密钥
     pub fn import_key(&self, name: &str, target: &str, format: Option<&str>) -> Result<String> {
-        let output = KuboCommand::import_key(self.app.clone(), name, target, format).run()?;
+        let output = KuboCommand::import_key(self.app.clone(), name, target, format)
+            .on_node(&self.node)
+            .run()?;
         if output.ret == 0 {
             Ok(output.stdout.trim().to_string())
         } else {
@@ -517,22 +613,11 @@ impl IpfsDaemon {
     // Content 操作
     // ============================================================
 
-    /// 添加目录到 IPFS
-    pub fn add_directory(&self, dir: &str) -> Result<String> {
-        let output = KuboCommand::add_directory(self.app.clone(), dir).run()?;
-        if output.ret == 0 {
-            Ok(output.stdout.trim().to_string())
-        } else {
-            Err(anyhow!(
-                "Failed to add directory: {}",
-                output.stderr
-            ))
-        }
-    }
-
     /// 获取文件 CID（不实际添加到 IPFS）
     pub fn get_file_cid(&self, file: &str) -> Result<String> {
-        let output = KuboCommand::get_file_cid(self.app.clone(), file).run()?;
+        let output = KuboCommand::get_file_cid(self.app.clone(), file)
+            .on_node(&self.node)
+            .run()?;
         if output.ret == 0 {
             Ok(output.stdout.trim().to_string())
         } else {
@@ -542,64 +627,272 @@ impl IpfsDaemon {
 
     /// 获取文件 CIDv0
     pub fn get_file_cid_v0(&self, file: &str) -> Result<String> {
-        let output = KuboCommand::get_file_cid_v0(self.app.clone(), file).run()?;
+        let output = KuboCommand::get_file_cid_v0(self.app.clone(), file)
+            .on_node(&self.node)
+            .run()?;
         if output.ret == 0 {
             Ok(output.stdout.trim().to_string())
         } else {
             Err(anyhow!("Failed to get file CIDv0: {}", output.stderr))
         }
     }
+}
+
+// ============================================================
+// IpfsClient — daemon 的轻量句柄
+// ============================================================
+
+/// 只含连接信息的 daemon 句柄，clone 代价低
+/// 在 IPFS 状态锁内取得后即可释放锁，再执行发布、pin 等耗时操作，避免阻塞状态查询和 shutdown
+#[derive(Clone)]
+pub struct IpfsClient {
+    app: AppHandle,
+    node: KuboNode,
+    /// daemon 运行中或使用外部节点时的 HTTP 后端；None 时回退到 Kubo CLI
+    http: Option<KuboHttpBackend>,
+}
+
+impl IpfsDaemon {
+    /// daemon 子进程运行中或使用外部节点时返回 HTTP 后端，否则返回 None（由调用方回退到 CLI）
+    fn online_http(&self) -> Option<KuboHttpBackend> {
+        if self.is_managed() {
+            self.daemon_child.as_ref()?;
+        }
+        self.http().ok()
+    }
+
+    /// 当前 daemon 的轻量句柄
+    pub fn client(&self) -> IpfsClient {
+        IpfsClient {
+            app: self.app.clone(),
+            node: self.node.clone(),
+            http: self.online_http(),
+        }
+    }
+}
+
+impl IpfsClient {
+    /// 需要在线 daemon 的操作使用的 HTTP 后端
+    fn http(&self) -> Result<&KuboHttpBackend> {
+        self.http
+            .as_ref()
+            .ok_or_else(|| anyhow!("IPFS daemon is not online"))
+    }
+
+    fn cli(&self) -> KuboCliBackend {
+        KuboCliBackend::new(self.app.clone(), self.node.clone())
+    }
 
     // ============================================================
-    // 静态配置数据
-    // 对应 Swift: IPFSDaemon.peers / IPFSDaemon.resolvers
+    // Pin
     // ============================================================
 
-    /// IPFS Peering Peers（硬编码，与原项目一致）
-    pub fn peers_json() -> String {
-        serde_json::json!([
-            {
-                "ID": "12D3KooWBJY6ZVV8Tk8UDDFMEqWoxn89Xc8wnpm8uBFSR3ijDkui",
-                "Addrs": [
-                    "/ip4/167.71.172.216/tcp/4001",
-                    "/ip6/2604:a880:800:10::826:1/tcp/4001",
-                    "/ip4/167.71.172.216/udp/4001/quic",
-                    "/ip6/2604:a880:800:10::826:1/udp/4001/quic"
-                ]
-            },
-            {
-                "ID": "12D3KooWDaGQ3Fu3iLgFxrrg5Vfef9z5L3DQZoyqFxQJbKKPnCc8",
-                "Addrs": [
-                    "/ip4/143.198.18.166/tcp/4001",
-                    "/ip6/2604:a880:800:10::735:7001/tcp/4001",
-                    "/ip4/143.198.18.166/udp/4001/quic",
-                    "/ip6/2604:a880:800:10::735:7001/udp/4001/quic"
-                ]
-            },
-            {
-                "ID": "12D3KooWJ6MTkNM8Bu8DzNiRm1GY3Wqh8U8Pp1zRWap6xY3MvsNw",
-                "Addrs": ["/dnsaddr/node-1.ipfs.bit.site"]
-            },
-            {
-                "ID": "12D3KooWQ85aSCFwFkByr5e3pUCQeuheVhobVxGSSs1DrRQHGv1t",
-                "Addrs": ["/dnsaddr/node-1.ipfs.4everland.net"]
-            },
-            {
-                "ID": "12D3KooWGtYkBAaqJMJEmywMxaCiNP7LCEFUAFiLEBASe232c2VH",
-                "Addrs": ["/dns4/bitswap.filebase.io/tcp/443/wss"]
+    /// pin CID 并报告进度：每收到一次进度回调一次已拉取的块数
+    /// 对应 pin/add?progress=true 的逐行 JSON 输出
+    pub async fn pin_with_progress<F>(&self, cid: &str, on_progress: F) -> Result<()>
+    where
+        F: Fn(u64),
+    {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), cid.into());
+        args.insert("progress".into(), "true".into());
+        let mut response = self
+            .http()?
+            .request("pin/add", Some(&args), Some(3600))
+            .await?;
+
+        let mut buffer = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=pos).collect();
+                match parse_pin_progress(&line)? {
+                    Some(PinProgress::Blocks(n)) => on_progress(n),
+                    Some(PinProgress::Done) => info!("Pinned {}", cid),
+                    None => {}
+                }
             }
-        ])
-        .to_string()
+        }
+        Ok(())
     }
 
-    /// DNS over HTTPS resolvers
-    pub fn resolvers_json() -> String {
-        serde_json::json!({
-            "bit.": "https://dweb-dns.v2ex.pro/dns-query",
-            "sol.": "https://dweb-dns.v2ex.pro/dns-query",
-            "fc.": "https://dweb-dns.v2ex.pro/dns-query",
-            "eth.": "https://dns.eth.limo/dns-query"
-        })
-        .to_string()
+    /// 列出本地 pin，pin_type 为 recursive / direct / indirect / all
+    pub async fn list_pins(&self, pin_type: &str) -> Result<IpfsPinned> {
+        let mut args = HashMap::new();
+        args.insert("type".into(), pin_type.into());
+        let data = self.http()?.call("pin/ls", Some(&args), Some(300)).await?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// 查询 CID 的 pin 类型，未 pin 时返回 None
+    pub async fn pin_type(&self, cid: &str) -> Result<Option<String>> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), cid.into());
+        match self.http()?.call("pin/ls", Some(&args), Some(60)).await {
+            Ok(data) => {
+                let pinned: IpfsPinned = serde_json::from_slice(&data)?;
+                Ok(pinned.keys.into_values().next().map(|info| info.pin_type))
+            }
+            Err(e) if e.to_string().contains("not pinned") => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 列出目录的直接子项，返回 名称 → CID
+    /// 一次请求取得整个目录（含分片目录），代替逐项 dag/resolve
+    pub async fn list_directory(&self, cid: &str) -> Result<HashMap<String, String>> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), cid.into());
+        args.insert("resolve-type".into(), "false".into());
+        args.insert("size".into(), "false".into());
+        let data = self.http()?.call("ls", Some(&args), Some(60)).await?;
+        let listing: IpfsLs = serde_json::from_slice(&data)?;
+        Ok(listing
+            .objects
+            .into_iter()
+            .flat_map(|object| object.links)
+            .map(|link| (link.name, link.hash))
+            .collect())
+    }
+
+    // ============================================================
+    // CAR 导入导出
+    // ============================================================
+
+    /// 将 CID 对应的 DAG 导出为 CAR 文件（流式写入），返回写入的字节数
+    pub async fn dag_export(&self, cid: &str, target: &Path) -> Result<u64> {
+        let mut args = HashMap::new();
+        args.insert("arg".into(), cid.into());
+        let mut response = self
+            .http()?
+            .request("dag/export", Some(&args), Some(3600))
+            .await?;
+
+        let mut file = tokio::fs::File::create(target).await?;
+        let mut written = 0u64;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        info!("Exported {} to {:?} ({} bytes)", cid, target, written);
+        Ok(written)
+    }
+
+    /// 导入 CAR 文件并 pin 其根，返回根 CID
+    pub async fn dag_import(&self, car: &Path) -> Result<Vec<String>> {
+        let roots = self.http()?.dag_import(car).await?;
+        info!("Imported {:?}, roots: {:?}", car, roots);
+        Ok(roots)
+    }
+}
+
+// ============================================================
+// IpfsBackend 实现
+// daemon 运行中时走 HTTP RPC（add 为流式上传并推送 "ipfs:add-progress"），
+// 离线时回退到 Kubo CLI
+// ============================================================
+
+impl IpfsBackend for IpfsClient {
+    async fn add_directory(&self, dir: &Path) -> Result<String> {
+        let Some(http) = &self.http else {
+            return self.cli().add_directory(dir).await;
+        };
+        let app = self.app.clone();
+        let path = dir.to_string_lossy().to_string();
+        let last_percent = Arc::new(AtomicU64::new(0));
+        let on_progress: AddProgressFn = Arc::new(move |bytes, total| {
+            // 每个百分点最多推送一次
+            let percent = (bytes * 100).checked_div(total).unwrap_or(100);
+            if percent > last_percent.swap(percent, Ordering::Relaxed) || bytes == total {
+                let progress = IpfsAddProgress {
+                    path: path.clone(),
+                    bytes,
+                    total,
+                };
+                let _ = app.emit("ipfs:add-progress", &progress);
+            }
+        });
+        http.add_directory_with_progress(dir, on_progress).await
+    }
+
+    /// 始终通过 Kubo CLI 的 --only-hash 计算，不经 HTTP 上传整个站点
+    async fn hash_directory(&self, dir: &Path) -> Result<String> {
+        self.cli().hash_directory(dir).await
+    }
+
+    async fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        match &self.http {
+            Some(http) => http.cat(path).await,
+            None => self.cli().cat(path).await,
+        }
     }
-}
\ No newline at end of file
+
+    async fn generate_key(&self, name: &str) -> Result<String> {
+        match &self.http {
+            Some(http) => http.generate_key(name).await,
+            None => self.cli().generate_key(name).await,
+        }
+    }
+
+    async fn remove_key(&self, name: &str) -> Result<()> {
+        match &self.http {
+            Some(http) => http.remove_key(name).await,
+            None => self.cli().remove_key(name).await,
+        }
+    }
+
+    async fn list_keys(&self) -> Result<Vec<IpfsKey>> {
+        match &self.http {
+            Some(http) => http.list_keys().await,
+            None => self.cli().list_keys().await,
+        }
+    }
+
+    /// 对应 Swift: IPFSDaemon.api(path: "name/publish")
+    async fn publish_name(
+        &self,
+        key_name: &str,
+        cid: &str,
+        options: IpnsPublishOptions,
+    ) -> Result<IpfsPublished> {
+        match &self.http {
+            Some(http) => http.publish_name(key_name, cid, options).await,
+            None => self.cli().publish_name(key_name, cid, options).await,
+        }
+    }
+
+    /// 对应 Swift: resolveIPNSorDNSLink(name:)
+    async fn resolve_name(&self, name: &str) -> Result<String> {
+        match &self.http {
+            Some(http) => http.resolve_name(name).await,
+            None => self.cli().resolve_name(name).await,
+        }
+    }
+
+    async fn pin(&self, cid: &str) -> Result<()> {
+        match &self.http {
+            Some(http) => http.pin(cid).await,
+            None => self.cli().pin(cid).await,
+        }
+    }
+
+    async fn unpin(&self, cid: &str) -> Result<()> {
+        match &self.http {
+            Some(http) => http.unpin(cid).await,
+            None => self.cli().unpin(cid).await,
+        }
+    }
+
+    /// 对应 Swift: getStatsBW()
+    async fn stats_bw(&self) -> Result<IpfsBandwidth> {
+        self.http()?.stats_bw().await
+    }
+
+    async fn repo_state(&self) -> Result<IpfsRepoState> {
+        match &self.http {
+            Some(http) => http.repo_state().await,
+            None => self.cli().repo_state().await,
+        }
+    }
+}
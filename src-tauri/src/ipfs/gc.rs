@@ -0,0 +1,147 @@
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tracing::{debug, error, info};
+
+use super::backend::IpfsBackend;
+use super::state::IpfsStateHandle;
+use crate::models::settings::{AppSettings, GcPolicy};
+use crate::store::PlanetStoreHandle;
+
+// ============================================================
+// 自动 GC
+// 按 AppSettings.gc_policy 定期检查，满足条件时执行 repo/gc
+// 持有 IPFS 锁并确认没有 Planet 正在发布后才会 GC，避免回收发布中的内容
+// ============================================================
+
+/// 检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 触发 GC 的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcTrigger {
+    Watermark,
+    Schedule,
+    Publishes,
+}
+
+/// 根据策略判断是否应当 GC
+/// usage_percent 为仓库用量占 StorageMax 的百分比，since_last_gc 为距上次 GC 的时间
+pub fn due(
+    policy: &GcPolicy,
+    usage_percent: Option<f64>,
+    publishes: u32,
+    since_last_gc: Duration,
+) -> Option<GcTrigger> {
+    if let (Some(watermark), Some(usage)) = (policy.watermark_percent, usage_percent) {
+        if usage >= watermark as f64 {
+            return Some(GcTrigger::Watermark);
+        }
+    }
+    if policy.after_publishes.is_some_and(|n| n > 0 && publishes >= n) {
+        return Some(GcTrigger::Publishes);
+    }
+    if policy
+        .interval_secs
+        .is_some_and(|secs| since_last_gc >= Duration::from_secs(secs))
+    {
+        return Some(GcTrigger::Schedule);
+    }
+    None
+}
+
+/// 在 main.rs setup 中调用，启动后台 GC 任务
+pub fn start(state: IpfsStateHandle, store: PlanetStoreHandle, app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_gc = Instant::now();
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            let policy = AppSettings::current(&app).gc_policy;
+            if !policy.is_enabled() {
+                continue;
+            }
+            if run_if_due(&state, &store, &app, &policy, last_gc.elapsed()).await {
+                last_gc = Instant::now();
+            }
+        }
+    });
+}
+
+/// 检查一次策略，执行了 GC 时返回 true
+async fn run_if_due(
+    state: &IpfsStateHandle,
+    store: &PlanetStoreHandle,
+    app: &AppHandle,
+    policy: &GcPolicy,
+    since_last_gc: Duration,
+) -> bool {
+    let mut s = state.lock().await;
+    // 外部节点上的内容不归应用管理，不自动 GC
+    if !s.is_online() || !s.daemon.is_managed() {
+        return false;
+    }
+
+    let usage_percent = if policy.watermark_percent.is_some() {
+        match s.daemon.client().repo_state().await {
+            Ok(repo) => repo.usage_percent(),
+            Err(e) => {
+                debug!("Failed to read repo stat: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let publishes = match store.lock() {
+        Ok(store) => {
+            if store.any_publishing() {
+                debug!("Skipping automatic GC while a publish is in progress");
+                return false;
+            }
+            store.publishes_since_gc
+        }
+        Err(_) => return false,
+    };
+
+    let Some(trigger) = due(policy, usage_percent, publishes, since_last_gc) else {
+        return false;
+    };
+    info!("Running automatic GC ({:?})", trigger);
+    match s.daemon.gc().await {
+        Ok(_) => {
+            if let Ok(mut store) = store.lock() {
+                store.publishes_since_gc = 0;
+            }
+            s.refresh_repo_state().await;
+            s.emit_state_changed(app);
+            true
+        }
+        Err(e) => {
+            error!("Automatic GC failed: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_checks_each_condition() {
+        let policy = GcPolicy {
+            watermark_percent: Some(90),
+            interval_secs: Some(3600),
+            after_publishes: Some(5),
+        };
+        let fresh = Duration::from_secs(10);
+        assert_eq!(due(&policy, Some(50.0), 0, fresh), None);
+        assert_eq!(due(&policy, Some(95.0), 0, fresh), Some(GcTrigger::Watermark));
+        assert_eq!(due(&policy, None, 5, fresh), Some(GcTrigger::Publishes));
+        assert_eq!(
+            due(&policy, None, 0, Duration::from_secs(3600)),
+            Some(GcTrigger::Schedule)
+        );
+        assert_eq!(due(&GcPolicy::default(), Some(100.0), 99, fresh), None);
+    }
+}
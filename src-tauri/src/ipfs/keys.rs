@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tracing::{error, info, warn};
+
+use super::backend::IpfsBackend;
+use crate::helpers::paths;
+
+// ============================================================
+// 待清理的 IPNS key
+// 删除 Planet 时 daemon 不在线或移除失败，key 名称记录到 OrphanKeys.json，
+// daemon 上线后由 ensure_planet_keys 统一移除
+// ============================================================
+
+/// 串行化对记录文件的读写
+static ORPHAN_KEYS: Mutex<()> = Mutex::new(());
+
+fn path(app: &AppHandle) -> PathBuf {
+    paths::get_data_path(app).join("OrphanKeys.json")
+}
+
+fn load(app: &AppHandle) -> Vec<String> {
+    fs::read_to_string(path(app))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, keys: &[String]) -> Result<()> {
+    let path = path(app);
+    if keys.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    fs::write(path, serde_json::to_string_pretty(keys)?)?;
+    Ok(())
+}
+
+/// 记录未能移除的 key，留待 daemon 上线后清理
+pub fn record_orphan(app: &AppHandle, key_name: &str) -> Result<()> {
+    let _guard = ORPHAN_KEYS.lock().map_err(|e| anyhow!("{}", e))?;
+    let mut keys = load(app);
+    if !keys.iter().any(|k| k == key_name) {
+        keys.push(key_name.to_string());
+    }
+    save(app, &keys)
+}
+
+/// 移除记录中的 key
+/// in_use 中的 key（如重新导入的同一 Planet）只从记录中删除，不移除
+pub async fn remove_orphans<B: IpfsBackend>(backend: &B, app: &AppHandle, in_use: &HashSet<String>) {
+    let pending = match ORPHAN_KEYS.lock() {
+        Ok(_guard) => load(app),
+        Err(_) => return,
+    };
+    if pending.is_empty() {
+        return;
+    }
+    let existing: HashSet<String> = match backend.list_keys().await {
+        Ok(keys) => keys.into_iter().map(|k| k.name).collect(),
+        Err(e) => {
+            warn!("Failed to list IPNS keys: {}", e);
+            return;
+        }
+    };
+
+    let mut done = Vec::new();
+    for key_name in pending {
+        if in_use.contains(&key_name) || !existing.contains(&key_name) {
+            done.push(key_name);
+            continue;
+        }
+        match backend.remove_key(&key_name).await {
+            Ok(()) => {
+                info!("Removed IPNS key of deleted planet: {}", key_name);
+                done.push(key_name);
+            }
+            Err(e) => warn!("Failed to remove IPNS key {}: {}", key_name, e),
+        }
+    }
+
+    // 期间可能有新记录，重新读取后只去掉已处理的 key
+    let Ok(_guard) = ORPHAN_KEYS.lock() else {
+        return;
+    };
+    let mut keys = load(app);
+    keys.retain(|k| !done.contains(k));
+    if let Err(e) = save(app, &keys) {
+        error!("Failed to save orphan IPNS keys: {}", e);
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// ============================================================
+// daemon 生命周期
+// Uninitialized → SettingUp → Starting → Online → Stopping → Offline
+// 任一步骤失败进入 Failed；只允许下表中的转换，防止并发操作互相覆盖状态
+// ============================================================
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DaemonLifecycle {
+    /// 尚未 setup（启动时、修复仓库后）
+    #[default]
+    Uninitialized,
+    SettingUp,
+    Starting,
+    Online,
+    Stopping,
+    Offline,
+    Failed { message: String },
+}
+
+impl DaemonLifecycle {
+    pub fn is_online(&self) -> bool {
+        matches!(self, Self::Online)
+    }
+
+    /// 正在进行 setup / launch / shutdown
+    pub fn is_busy(&self) -> bool {
+        matches!(self, Self::SettingUp | Self::Starting | Self::Stopping)
+    }
+
+    pub fn error_message(&self) -> Option<&str> {
+        match self {
+            Self::Failed { message } => Some(message),
+            _ => None,
+        }
+    }
+
+    pub fn can_transition(&self, to: &Self) -> bool {
+        use DaemonLifecycle::*;
+        matches!(
+            (self, to),
+            (Uninitialized | Offline | Failed { .. }, SettingUp)
+                | (SettingUp, Starting | Uninitialized | Failed { .. })
+                | (Offline | Failed { .. }, Starting)
+                | (Starting, Online | Failed { .. })
+                | (Online, Stopping | Offline | Failed { .. })
+                // 启动失败后仍可能残留子进程，允许关闭
+                // 启动期间 state::start 一直持有锁，Starting 无法被打断，不允许直接关闭
+                | (Failed { .. }, Stopping)
+                | (Stopping, Offline | Failed { .. })
+                // 刷新状态时发现节点已可访问（例如外部节点恢复）
+                | (Offline | Failed { .. }, Online)
+                // 重启失败时更新错误信息
+                | (Failed { .. }, Failed { .. })
+        )
+    }
+}
+
+impl fmt::Display for DaemonLifecycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Uninitialized => "uninitialized",
+            Self::SettingUp => "setting up",
+            Self::Starting => "starting",
+            Self::Online => "online",
+            Self::Stopping => "stopping",
+            Self::Offline => "offline",
+            Self::Failed { .. } => "failed",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DaemonLifecycle::*;
+
+    #[test]
+    fn test_transitions() {
+        let failed = Failed {
+            message: "boom".into(),
+        };
+        let path = [Uninitialized, SettingUp, Starting, Online, Stopping, Offline, Starting];
+        for pair in path.windows(2) {
+            assert!(pair[0].can_transition(&pair[1]), "{} -> {}", pair[0], pair[1]);
+        }
+        assert!(Starting.can_transition(&failed));
+        assert!(failed.can_transition(&SettingUp));
+        assert!(failed.can_transition(&Stopping));
+
+        // 并发的第二次 setup / launch 会被拒绝
+        assert!(!SettingUp.can_transition(&SettingUp));
+        assert!(!Starting.can_transition(&Starting));
+        assert!(!Uninitialized.can_transition(&Starting));
+        assert!(!Online.can_transition(&SettingUp));
+        assert!(!Stopping.can_transition(&Online));
+        assert!(!Starting.can_transition(&Stopping));
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+// ============================================================
+// Daemon 日志缓冲
+// 保存 Kubo daemon 最近的 stdout/stderr 输出，供前端查看和导出
+// 独立于 IpfsState 的锁，daemon 启动过程中也能读取
+// ============================================================
+
+/// 默认保留的日志行数
+pub const DEFAULT_LOG_CAPACITY: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// 一行 daemon 输出 — "ipfs:log" 事件的 payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsLogLine {
+    pub timestamp: DateTime<Utc>,
+    pub stream: LogStream,
+    pub line: String,
+}
+
+/// ipfs_get_logs 的过滤条件，字段均可省略
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LogFilter {
+    pub stream: Option<LogStream>,
+    /// 不区分大小写的子串匹配
+    pub contains: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    /// 只返回最后 limit 行
+    pub limit: Option<usize>,
+}
+
+pub struct DaemonLogs {
+    capacity: usize,
+    lines: Mutex<VecDeque<IpfsLogLine>>,
+}
+
+/// 全局日志缓冲的类型别名
+pub type DaemonLogsHandle = Arc<DaemonLogs>;
+
+impl DaemonLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// 追加一行，超出容量时丢弃最旧的行
+    pub fn push(&self, stream: LogStream, line: String) -> IpfsLogLine {
+        let entry = IpfsLogLine {
+            timestamp: Utc::now(),
+            stream,
+            line,
+        };
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() == self.capacity {
+                lines.pop_front();
+            }
+            lines.push_back(entry.clone());
+        }
+        entry
+    }
+
+    /// 按条件查询，结果按时间顺序
+    pub fn query(&self, filter: &LogFilter) -> Vec<IpfsLogLine> {
+        let Ok(lines) = self.lines.lock() else {
+            return Vec::new();
+        };
+        let needle = filter.contains.as_ref().map(|c| c.to_lowercase());
+        let mut matched: Vec<IpfsLogLine> = lines
+            .iter()
+            .filter(|l| filter.stream.is_none_or(|s| l.stream == s))
+            .filter(|l| filter.since.is_none_or(|t| l.timestamp >= t))
+            .filter(|l| {
+                needle
+                    .as_ref()
+                    .is_none_or(|n| l.line.to_lowercase().contains(n.as_str()))
+            })
+            .cloned()
+            .collect();
+        if let Some(limit) = filter.limit {
+            let skip = matched.len().saturating_sub(limit);
+            matched.drain(..skip);
+        }
+        matched
+    }
+
+    /// 某个输出流自 since 以来的最后 n 行文本
+    pub fn tail(&self, stream: LogStream, since: Option<DateTime<Utc>>, n: usize) -> Vec<String> {
+        self.query(&LogFilter {
+            stream: Some(stream),
+            since,
+            limit: Some(n),
+            ..Default::default()
+        })
+        .into_iter()
+        .map(|l| l.line)
+        .collect()
+    }
+
+    /// 将全部缓冲写入文件
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content: String = self
+            .query(&LogFilter::default())
+            .iter()
+            .map(|l| {
+                format!(
+                    "{} [{}] {}\n",
+                    l.timestamp.to_rfc3339(),
+                    match l.stream {
+                        LogStream::Stdout => "stdout",
+                        LogStream::Stderr => "stderr",
+                    },
+                    l.line
+                )
+            })
+            .collect();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_drops_oldest_lines() {
+        let logs = DaemonLogs::new(3);
+        for i in 0..5 {
+            logs.push(LogStream::Stdout, format!("line {}", i));
+        }
+        let lines: Vec<String> = logs
+            .query(&LogFilter::default())
+            .into_iter()
+            .map(|l| l.line)
+            .collect();
+        assert_eq!(lines, vec!["line 2", "line 3", "line 4"]);
+    }
+
+    #[test]
+    fn test_query_filters_by_stream_text_and_limit() {
+        let logs = DaemonLogs::new(10);
+        logs.push(LogStream::Stdout, "Swarm listening".into());
+        logs.push(LogStream::Stderr, "ERROR first".into());
+        logs.push(LogStream::Stderr, "warning".into());
+        logs.push(LogStream::Stderr, "error second".into());
+
+        let errors = logs.query(&LogFilter {
+            stream: Some(LogStream::Stderr),
+            contains: Some("error".into()),
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, "error second");
+        assert_eq!(logs.tail(LogStream::Stdout, None, 5), vec!["Swarm listening"]);
+    }
+}
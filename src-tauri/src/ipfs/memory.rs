@@ -0,0 +1,247 @@
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::backend::{IpfsBackend, IpfsKey};
+use super::models::{IpfsBandwidth, IpfsPublished, IpfsRepoState, IpnsPublishOptions};
+
+// ============================================================
+// 内存中的 IPFS 后端（仅测试使用）
+// CID 与 Key ID 由内容 / 名称的 FNV 哈希确定，同样的输入总得到同样的结果
+// ============================================================
+
+#[derive(Default)]
+struct MemoryState {
+    /// 根 CID → 相对路径 → 文件内容
+    trees: HashMap<String, BTreeMap<String, Vec<u8>>>,
+    /// 密钥名 → Key ID
+    keys: BTreeMap<String, String>,
+    /// Key ID → 发布的 CID
+    names: HashMap<String, String>,
+    pins: HashSet<String>,
+    bytes_in: i64,
+    bytes_out: i64,
+}
+
+#[derive(Default)]
+pub struct MemoryBackend {
+    state: Mutex<MemoryState>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_pinned(&self, cid: &str) -> bool {
+        self.state.lock().unwrap().pins.contains(cid)
+    }
+
+    /// 某个 Key ID 当前指向的 CID
+    pub fn published(&self, key_id: &str) -> Option<String> {
+        self.state.lock().unwrap().names.get(key_id).cloned()
+    }
+
+    fn hash_tree(tree: &BTreeMap<String, Vec<u8>>) -> String {
+        let mut hash = FNV_OFFSET;
+        for (path, content) in tree {
+            hash = fnv1a(hash, path.as_bytes());
+            hash = fnv1a(hash, &[0]);
+            hash = fnv1a(hash, content);
+        }
+        format!("bafymem{:016x}", hash)
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn read_tree(dir: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    fn walk(dir: &Path, prefix: &str, tree: &mut BTreeMap<String, Vec<u8>>) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            if entry.file_type()?.is_dir() {
+                walk(&entry.path(), &format!("{}/", name), tree)?;
+            } else {
+                tree.insert(name, fs::read(entry.path())?);
+            }
+        }
+        Ok(())
+    }
+    let mut tree = BTreeMap::new();
+    walk(dir, "", &mut tree)?;
+    Ok(tree)
+}
+
+impl IpfsBackend for MemoryBackend {
+    async fn add_directory(&self, dir: &Path) -> Result<String> {
+        let tree = read_tree(dir)?;
+        let cid = Self::hash_tree(&tree);
+        let mut state = self.state.lock().unwrap();
+        state.bytes_in += tree.values().map(|c| c.len() as i64).sum::<i64>();
+        state.trees.insert(cid.clone(), tree);
+        state.pins.insert(cid.clone());
+        Ok(cid)
+    }
+
+    async fn hash_directory(&self, dir: &Path) -> Result<String> {
+        Ok(Self::hash_tree(&read_tree(dir)?))
+    }
+
+    async fn cat(&self, path: &str) -> Result<Vec<u8>> {
+        let path = path.trim_start_matches("/ipfs/");
+        let (cid, file) = path.split_once('/').unwrap_or((path, ""));
+        let mut state = self.state.lock().unwrap();
+        let content = state
+            .trees
+            .get(cid)
+            .and_then(|tree| tree.get(file))
+            .cloned()
+            .ok_or_else(|| anyhow!("{} not found", path))?;
+        state.bytes_out += content.len() as i64;
+        Ok(content)
+    }
+
+    async fn generate_key(&self, name: &str) -> Result<String> {
+        let mut state = self.state.lock().unwrap();
+        if state.keys.contains_key(name) {
+            return Err(anyhow!("key with name '{}' already exists", name));
+        }
+        let id = format!("k51mem{:016x}", fnv1a(FNV_OFFSET, name.as_bytes()));
+        state.keys.insert(name.to_string(), id.clone());
+        Ok(id)
+    }
+
+    async fn remove_key(&self, name: &str) -> Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .keys
+            .remove(name)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("no key named {} was found", name))
+    }
+
+    async fn list_keys(&self) -> Result<Vec<IpfsKey>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .keys
+            .iter()
+            .map(|(name, id)| IpfsKey {
+                name: name.clone(),
+                id: id.clone(),
+            })
+            .collect())
+    }
+
+    async fn publish_name(
+        &self,
+        key_name: &str,
+        cid: &str,
+        _options: IpnsPublishOptions,
+    ) -> Result<IpfsPublished> {
+        let mut state = self.state.lock().unwrap();
+        let id = state
+            .keys
+            .get(key_name)
+            .cloned()
+            .ok_or_else(|| anyhow!("no key by the given name was found"))?;
+        state.names.insert(id.clone(), cid.to_string());
+        Ok(IpfsPublished {
+            name: id,
+            value: format!("/ipfs/{}", cid),
+        })
+    }
+
+    async fn resolve_name(&self, name: &str) -> Result<String> {
+        let name = name.trim_start_matches("/ipns/");
+        self.published(name)
+            .ok_or_else(|| anyhow!("could not resolve name {}", name))
+    }
+
+    async fn pin(&self, cid: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.trees.contains_key(cid) {
+            return Err(anyhow!("{} not found", cid));
+        }
+        state.pins.insert(cid.to_string());
+        Ok(())
+    }
+
+    async fn unpin(&self, cid: &str) -> Result<()> {
+        if self.state.lock().unwrap().pins.remove(cid) {
+            Ok(())
+        } else {
+            Err(anyhow!("not pinned or pinned indirectly"))
+        }
+    }
+
+    async fn stats_bw(&self) -> Result<IpfsBandwidth> {
+        let state = self.state.lock().unwrap();
+        Ok(IpfsBandwidth {
+            total_in: state.bytes_in,
+            total_out: state.bytes_out,
+            rate_in: 0.0,
+            rate_out: 0.0,
+        })
+    }
+
+    async fn repo_state(&self) -> Result<IpfsRepoState> {
+        let state = self.state.lock().unwrap();
+        Ok(IpfsRepoState {
+            repo_size: state
+                .trees
+                .values()
+                .flat_map(|t| t.values())
+                .map(|c| c.len() as i64)
+                .sum(),
+            storage_max: 0,
+            num_objects: state.trees.values().map(|t| t.len() as i64).sum(),
+            repo_path: "memory".into(),
+            version: "fs-repo@0".into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_add_cat_and_publish_round_trip() {
+        let dir = std::env::temp_dir().join(format!("planet-mem-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("post")).unwrap();
+        fs::write(dir.join("post").join("index.html"), "hello").unwrap();
+
+        let backend = MemoryBackend::new();
+        let hashed = backend.hash_directory(&dir).await.unwrap();
+        let cid = backend.add_directory(&dir).await.unwrap();
+        assert_eq!(hashed, cid);
+        assert!(backend.is_pinned(&cid));
+        assert_eq!(
+            backend.cat(&format!("/ipfs/{}/post/index.html", cid)).await.unwrap(),
+            b"hello"
+        );
+
+        let key = backend.generate_key("planet").await.unwrap();
+        let options = IpnsPublishOptions {
+            lifetime: std::time::Duration::from_secs(60),
+            ttl: std::time::Duration::from_secs(60),
+        };
+        backend.publish_name("planet", &cid, options).await.unwrap();
+        assert_eq!(backend.resolve_name(&key).await.unwrap(), cid);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
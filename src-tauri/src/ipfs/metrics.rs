@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, error};
+
+use super::backend::IpfsBackend;
+use super::state::IpfsStateHandle;
+
+// ============================================================
+// 带宽与 Peer 指标
+// daemon 在线时定期采样 stats/bw 和 swarm/peers，保留约一小时的历史
+// ============================================================
+
+/// 采样间隔
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 保留的采样数（10 秒 × 360 = 1 小时）
+pub const HISTORY_CAPACITY: usize = 360;
+
+/// 单次采样 — "ipfs:metrics" 事件的 payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsMetricsSample {
+    pub timestamp: DateTime<Utc>,
+    pub rate_in: f64,
+    pub rate_out: f64,
+    pub total_in: i64,
+    pub total_out: i64,
+    pub peer_count: usize,
+}
+
+pub struct MetricsHistory {
+    capacity: usize,
+    samples: Mutex<VecDeque<IpfsMetricsSample>>,
+}
+
+/// 全局指标历史的类型别名
+pub type MetricsHandle = Arc<MetricsHistory>;
+
+impl MetricsHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// 追加采样，超出容量时丢弃最旧的
+    pub fn push(&self, sample: IpfsMetricsSample) {
+        if let Ok(mut samples) = self.samples.lock() {
+            if samples.len() == self.capacity {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+        }
+    }
+
+    /// 全部历史，按时间顺序
+    pub fn history(&self) -> Vec<IpfsMetricsSample> {
+        self.samples
+            .lock()
+            .map(|s| s.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// 在 main.rs setup 中调用，启动后台采样任务
+pub fn start(state: IpfsStateHandle, metrics: MetricsHandle, app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+            if let Some(sample) = sample(&state).await {
+                metrics.push(sample.clone());
+                if let Err(e) = app.emit("ipfs:metrics", &sample) {
+                    error!("Failed to emit ipfs metrics: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// 采样一次；daemon 离线或正被其他操作占用时跳过
+async fn sample(state: &IpfsStateHandle) -> Option<IpfsMetricsSample> {
+    let s = state.try_lock().ok()?;
+    if !s.is_online() {
+        return None;
+    }
+    let bandwidth = match s.daemon.client().stats_bw().await {
+        Ok(bw) => bw,
+        Err(e) => {
+            debug!("Failed to sample bandwidth: {}", e);
+            return None;
+        }
+    };
+    let peer_count = s.daemon.get_peer_count().await.unwrap_or(0);
+    Some(IpfsMetricsSample {
+        timestamp: Utc::now(),
+        rate_in: bandwidth.rate_in,
+        rate_out: bandwidth.rate_out,
+        total_in: bandwidth.total_in,
+        total_out: bandwidth.total_out,
+        peer_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_keeps_latest_samples() {
+        let history = MetricsHistory::new(2);
+        for peers in 0..3 {
+            history.push(IpfsMetricsSample {
+                timestamp: Utc::now(),
+                rate_in: 0.0,
+                rate_out: 0.0,
+                total_in: 0,
+                total_out: 0,
+                peer_count: peers,
+            });
+        }
+        let peers: Vec<usize> = history.history().iter().map(|s| s.peer_count).collect();
+        assert_eq!(peers, vec![1, 2]);
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use super::command::KuboCommand;
+use super::models::IpfsVersion;
+use super::repo;
+use crate::helpers::paths;
+
+// ============================================================
+// Kubo 仓库版本检查与迁移
+// 内置的 Kubo 比磁盘上的仓库新时，daemon 会拒绝启动
+// setup 时先比较版本，需要时备份仓库并执行 `ipfs repo migrate`
+// 迁移程序（fs-repo-N-to-M）随应用打包（resources/bin/migrations），由 KuboCommand 加入 PATH
+// 打包步骤见 ai_code_ref/PHASE0_STEPS.md 4.5
+// ============================================================
+
+/// 仓库版本与 Kubo 期望版本的比较结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoMigration {
+    UpToDate,
+    Upgrade { from: u32, to: u32 },
+    /// 仓库比内置 Kubo 新（例如曾被更新版本的 Kubo 打开过），无法降级
+    TooNew { repo: u32, supported: u32 },
+}
+
+pub fn plan(repo_version: u32, expected: u32) -> RepoMigration {
+    match repo_version.cmp(&expected) {
+        std::cmp::Ordering::Equal => RepoMigration::UpToDate,
+        std::cmp::Ordering::Less => RepoMigration::Upgrade {
+            from: repo_version,
+            to: expected,
+        },
+        std::cmp::Ordering::Greater => RepoMigration::TooNew {
+            repo: repo_version,
+            supported: expected,
+        },
+    }
+}
+
+/// 读取磁盘上的仓库版本（{repo}/version）
+pub fn repo_version(repo_path: &Path) -> Result<u32> {
+    let content = fs::read_to_string(repo_path.join("version"))?;
+    content
+        .trim()
+        .parse()
+        .map_err(|e| anyhow!("Invalid repo version {:?}: {}", content.trim(), e))
+}
+
+/// 从 from 迁移到 to 所需的迁移程序名称，每次升一个版本
+pub fn migration_names(from: u32, to: u32) -> Vec<String> {
+    (from..to)
+        .map(|v| {
+            let name = format!("fs-repo-{}-to-{}", v, v + 1);
+            if cfg!(windows) {
+                format!("{}.exe", name)
+            } else {
+                name
+            }
+        })
+        .collect()
+}
+
+/// 在 dirs 中查找缺少的迁移程序
+pub fn missing_migrations(dirs: &[PathBuf], from: u32, to: u32) -> Vec<String> {
+    migration_names(from, to)
+        .into_iter()
+        .filter(|name| !dirs.iter().any(|dir| dir.join(name).is_file()))
+        .collect()
+}
+
+/// 内置 Kubo 期望的仓库版本
+pub async fn expected_version(app: &AppHandle) -> Result<u32> {
+    let output = KuboCommand::ipfs_version_json(app.clone()).run_async().await?;
+    if output.ret != 0 {
+        return Err(anyhow!("Failed to read Kubo version: {}", output.stderr.trim()));
+    }
+    let version: IpfsVersion = serde_json::from_str(&output.stdout)?;
+    version
+        .repo
+        .parse()
+        .map_err(|e| anyhow!("Invalid Kubo repo version {:?}: {}", version.repo, e))
+}
+
+/// 检查仓库版本，需要时备份并迁移
+/// 备份和迁移都在阻塞线程池中执行，不阻塞 async 运行时
+pub async fn ensure_repo_version(app: &AppHandle, repo_path: &Path) -> Result<()> {
+    let current = repo_version(repo_path)?;
+    let expected = expected_version(app).await?;
+
+    match plan(current, expected) {
+        RepoMigration::UpToDate => Ok(()),
+        RepoMigration::TooNew { repo, supported } => Err(anyhow!(
+            "IPFS repo version {} is newer than the bundled Kubo supports ({}). \
+             The repo was likely opened by a newer Kubo; downgrading is not supported.",
+            repo,
+            supported
+        )),
+        RepoMigration::Upgrade { from, to } => {
+            let migrate = KuboCommand::repo_migrate(app.clone());
+            let dirs: Vec<PathBuf> = std::env::split_paths(&migrate.search_path()).collect();
+            let missing = missing_migrations(&dirs, from, to);
+            if !missing.is_empty() {
+                return Err(anyhow!(
+                    "IPFS repo version {} needs migration to {}, but the migration programs \
+                     are missing: {}. Expected them in {}",
+                    from,
+                    to,
+                    missing.join(", "),
+                    paths::get_migrations_path(app).display()
+                ));
+            }
+
+            info!("Migrating IPFS repo from version {} to {}...", from, to);
+            let backup = {
+                let repo_path = repo_path.to_path_buf();
+                let label = format!("v{}", from);
+                tokio::task::spawn_blocking(move || repo::backup(&repo_path, &label))
+                    .await?
+                    .map_err(|e| anyhow!("Failed to back up IPFS repo before migration: {}", e))?
+            };
+
+            let output = migrate.run_async().await?;
+            if output.ret != 0 {
+                warn!("Repo migration failed: {}", output.stderr);
+                return Err(anyhow!(
+                    "Failed to migrate IPFS repo from version {} to {}: {}. \
+                     A backup of the original repo is at {}",
+                    from,
+                    to,
+                    output.stderr.trim(),
+                    backup.display()
+                ));
+            }
+
+            let migrated = repo_version(repo_path)?;
+            if migrated != to {
+                return Err(anyhow!(
+                    "IPFS repo is at version {} after migration, expected {}. \
+                     A backup of the original repo is at {}",
+                    migrated,
+                    to,
+                    backup.display()
+                ));
+            }
+            info!("IPFS repo migrated to version {}", to);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_compares_versions() {
+        assert_eq!(plan(15, 15), RepoMigration::UpToDate);
+        assert_eq!(plan(12, 15), RepoMigration::Upgrade { from: 12, to: 15 });
+        assert_eq!(
+            plan(16, 15),
+            RepoMigration::TooNew {
+                repo: 16,
+                supported: 15
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_migrations_lists_each_step() {
+        let dir = std::env::temp_dir().join(format!("planet-migrations-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let names = migration_names(14, 16);
+        assert_eq!(names.len(), 2);
+        fs::write(dir.join(&names[0]), b"").unwrap();
+
+        let dirs = vec![dir.clone()];
+        assert_eq!(missing_migrations(&dirs, 14, 16), vec![names[1].clone()]);
+        assert!(missing_migrations(&dirs, 14, 15).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,4 +1,20 @@
+pub mod backend;
+pub mod binary;
+pub mod car;
 pub mod command;
 pub mod daemon;
+pub mod gc;
+pub mod keys;
+pub mod lifecycle;
+pub mod logs;
+#[cfg(test)]
+pub mod memory;
+pub mod metrics;
+pub mod migration;
+pub mod pinning;
+pub mod pins;
+pub mod profile;
+pub mod repo;
 pub mod state;
-pub mod models;
\ No newline at end of file
+pub mod models;
+pub mod watchdog;
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
+
+use super::lifecycle::DaemonLifecycle;
 
 // ============================================================
 // IPFS API 响应结构体
@@ -7,14 +10,18 @@ use std::collections::HashMap;
 // ============================================================
 
 /// IPFS 版本信息 — 对应 /api/v0/version
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IpfsVersion {
     #[serde(rename = "Version")]
     pub version: String,
+    #[serde(rename = "Commit", default)]
+    pub commit: String,
     #[serde(rename = "Repo")]
     pub repo: String,
     #[serde(rename = "System")]
     pub system: String,
+    #[serde(rename = "Golang", default)]
+    pub golang: String,
 }
 
 /// IPFS 仓库状态 — 对应 /api/v0/repo/stat
@@ -32,6 +39,13 @@ pub struct IpfsRepoState {
     pub version: String,
 }
 
+impl IpfsRepoState {
+    /// 已用空间占 StorageMax 的百分比；未设置上限时返回 None
+    pub fn usage_percent(&self) -> Option<f64> {
+        (self.storage_max > 0).then(|| self.repo_size as f64 * 100.0 / self.storage_max as f64)
+    }
+}
+
 /// IPFS 节点 ID 信息 — 对应 /api/v0/id
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IpfsId {
@@ -50,10 +64,39 @@ pub struct IpfsPeers {
     pub peers: Option<Vec<IpfsPeer>>,
 }
 
+/// 单个 peer — verbose 模式下额外包含 latency / muxer / direction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IpfsPeer {
     #[serde(rename = "Addr")]
     pub addr: Option<String>,
+    #[serde(rename = "Peer", default, skip_serializing_if = "Option::is_none")]
+    pub peer: Option<String>,
+    /// 如 "23.4ms"，未测得时为空字符串
+    #[serde(rename = "Latency", default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<String>,
+    #[serde(rename = "Muxer", default, skip_serializing_if = "Option::is_none")]
+    pub muxer: Option<String>,
+    /// 1 = inbound, 2 = outbound
+    #[serde(rename = "Direction", default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<i32>,
+}
+
+/// Peering.Peers 中的一项，connected 表示当前是否已连接
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsPeeringPeer {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Addrs", default)]
+    pub addrs: Vec<String>,
+    #[serde(default)]
+    pub connected: bool,
+}
+
+/// swarm/connect、swarm/disconnect 的响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsSwarmResult {
+    #[serde(rename = "Strings", default)]
+    pub strings: Vec<String>,
 }
 
 /// IPFS Name Publish 结果 — 对应 /api/v0/name/publish
@@ -98,6 +141,27 @@ pub struct IpfsPinInfo {
     pub pin_type: String,
 }
 
+/// IPFS 目录列表 — 对应 /api/v0/ls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsLs {
+    #[serde(rename = "Objects", default)]
+    pub objects: Vec<IpfsLsObject>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsLsObject {
+    #[serde(rename = "Links", default)]
+    pub links: Vec<IpfsLsLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsLsLink {
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Hash")]
+    pub hash: String,
+}
+
 // ============================================================
 // 应用级模型
 // ============================================================
@@ -118,14 +182,60 @@ pub struct ServerInfo {
 
 /// 前端可见的 IPFS 状态快照
 /// 通过 app.emit("ipfs:state-changed", &state) 推送
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IpfsStateSnapshot {
+    /// 由 lifecycle 派生，保留给只关心在线与否的调用方
     pub online: bool,
     pub is_operating: bool,
+    pub lifecycle: DaemonLifecycle,
     pub api_port: u16,
     pub gateway_port: u16,
     pub swarm_port: u16,
     pub repo_size: Option<i64>,
+    /// 仓库容量上限（Datastore.StorageMax，字节）
+    pub storage_max: Option<i64>,
+    /// false 表示使用外部 Kubo 节点，不能由应用启动或关闭
+    pub managed: bool,
+    /// 外部节点的网关地址；为空时使用本机 gateway_port
+    pub gateway_url: Option<String>,
     pub server_info: Option<ServerInfo>,
     pub error_message: Option<String>,
-}
\ No newline at end of file
+}
+
+/// ipfs_repo_stat 返回的仓库与版本信息
+/// online 为 false 时数据来自 Kubo CLI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsRepoStat {
+    pub online: bool,
+    pub repo: IpfsRepoState,
+    pub version: IpfsVersion,
+}
+
+/// add 上传进度事件 — "ipfs:add-progress"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsAddProgress {
+    /// 正在添加的本地目录
+    pub path: String,
+    /// 已发送的文件字节数
+    pub bytes: u64,
+    pub total: u64,
+}
+
+/// name/publish 的记录参数
+#[derive(Debug, Clone, Copy)]
+pub struct IpnsPublishOptions {
+    /// 记录有效期
+    pub lifetime: Duration,
+    /// 解析方可缓存记录的时长
+    pub ttl: Duration,
+}
+
+/// daemon 意外离线事件 — "ipfs:offline"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsOfflineEvent {
+    /// exited / health_check_failed
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub stderr_tail: Vec<String>,
+}
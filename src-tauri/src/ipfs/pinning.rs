@@ -0,0 +1,425 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+// ============================================================
+// IPFS Pinning Service API 客户端
+// 远程 pin，对应 IpfsDaemon::pin / unpin 的本地 pin
+// 规范：https://ipfs.github.io/pinning-services-api-spec/
+// ============================================================
+
+/// 轮询 pin 状态的默认间隔
+pub const PIN_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 轮询 pin 状态的默认最长时间
+pub const PIN_POLL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Pin 状态 — 对应规范中的 PinStatus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinStatus {
+    pub requestid: String,
+    /// queued / pinning / pinned / failed
+    pub status: String,
+    pub created: DateTime<Utc>,
+    pub pin: Pin,
+}
+
+impl PinStatus {
+    /// 是否已到达终态（pinned 或 failed）
+    pub fn is_settled(&self) -> bool {
+        self.status == "pinned" || self.status == "failed"
+    }
+}
+
+/// Pin 对象 — 对应规范中的 Pin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pin {
+    pub cid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Pin 列表 — 对应规范中的 PinResults
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinResults {
+    pub count: usize,
+    pub results: Vec<PinStatus>,
+}
+
+/// Planet 配置的远程 Pinning 服务
+/// request_id 之后的字段记录最近一次发布的 pin 状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinningService {
+    /// 服务显示名，同一 Planet 内唯一
+    pub name: String,
+    pub endpoint: String,
+    pub access_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pin_cid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pin_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pin_status_retrieved: Option<DateTime<Utc>>,
+}
+
+impl PinningService {
+    pub fn client(&self) -> PinningClient {
+        PinningClient::new(&self.endpoint, &self.access_token)
+    }
+}
+
+pub struct PinningClient {
+    http: Client,
+    endpoint: String,
+    token: String,
+}
+
+impl PinningClient {
+    /// endpoint 为服务根地址，如 "https://api.filebase.io/v1/ipfs"
+    pub fn new(endpoint: &str, token: &str) -> Self {
+        Self {
+            http: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("Failed to create HTTP client"),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    /// 提交 CID 进行 pin
+    pub async fn pin(&self, cid: &str, name: &str) -> Result<PinStatus> {
+        let response = self
+            .http
+            .post(format!("{}/pins", self.endpoint))
+            .bearer_auth(&self.token)
+            .json(&serde_json::json!({ "cid": cid, "name": name }))
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+
+    /// 按 name 列出所有状态的 pin
+    pub async fn list_pins(&self, name: &str) -> Result<Vec<PinStatus>> {
+        let response = self
+            .http
+            .get(format!("{}/pins", self.endpoint))
+            .bearer_auth(&self.token)
+            .query(&[
+                ("name", name),
+                ("status", "queued,pinning,pinned,failed"),
+                ("limit", "1000"),
+            ])
+            .send()
+            .await?;
+        let results: PinResults = Self::parse(response).await?;
+        Ok(results.results)
+    }
+
+    /// 查询单个 pin 的状态
+    pub async fn get_pin(&self, request_id: &str) -> Result<PinStatus> {
+        let response = self
+            .http
+            .get(format!("{}/pins/{}", self.endpoint, request_id))
+            .bearer_auth(&self.token)
+            .send()
+            .await?;
+        Self::parse(response).await
+    }
+
+    /// 删除 pin
+    pub async fn delete_pin(&self, request_id: &str) -> Result<()> {
+        let response = self
+            .http
+            .delete(format!("{}/pins/{}", self.endpoint, request_id))
+            .bearer_auth(&self.token)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Pinning service error: {} - {}", status, body));
+        }
+        Ok(())
+    }
+
+    /// pin 新的 CID，等到 pinned 后再删除同名的旧 pin
+    /// 新 pin 失败或超时时保留旧 pin，避免远程服务上没有任何可用版本
+    /// on_submitted 在提交后回调一次，之后每次状态变化回调 on_status
+    pub async fn replace_pin<S, F>(
+        &self,
+        cid: &str,
+        name: &str,
+        interval: Duration,
+        timeout: Duration,
+        on_submitted: S,
+        on_status: F,
+    ) -> Result<PinStatus>
+    where
+        S: FnOnce(&PinStatus),
+        F: Fn(&PinStatus),
+    {
+        let submitted = self.pin(cid, name).await?;
+        info!("Remote pin submitted: {} ({})", cid, submitted.requestid);
+        on_submitted(&submitted);
+
+        let status = if submitted.is_settled() {
+            submitted
+        } else {
+            self.wait_for_pin(&submitted.requestid, interval, timeout, on_status)
+                .await?
+        };
+        if status.status == "pinned" {
+            self.remove_previous_pins(name, &status).await?;
+        }
+        Ok(status)
+    }
+
+    /// 删除同名且早于 current 提交的旧 pin
+    /// 只删除更早的请求，较晚发布的 pin 不会被仍在轮询的旧任务删除
+    async fn remove_previous_pins(&self, name: &str, current: &PinStatus) -> Result<()> {
+        for old in self.list_pins(name).await? {
+            if old.requestid == current.requestid
+                || old.pin.cid == current.pin.cid
+                || old.created >= current.created
+            {
+                continue;
+            }
+            match self.delete_pin(&old.requestid).await {
+                Ok(()) => info!("Removed previous remote pin: {}", old.pin.cid),
+                Err(e) => warn!("Failed to remove remote pin {}: {}", old.requestid, e),
+            }
+        }
+        Ok(())
+    }
+
+    /// 轮询 pin 状态直到 pinned 或 failed
+    /// 每次状态变化都会回调 on_status；超过 timeout 仍未完成时返回错误
+    pub async fn wait_for_pin<F>(
+        &self,
+        request_id: &str,
+        interval: Duration,
+        timeout: Duration,
+        on_status: F,
+    ) -> Result<PinStatus>
+    where
+        F: Fn(&PinStatus),
+    {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut last_status = String::new();
+        loop {
+            let status = self.get_pin(request_id).await?;
+            if status.status != last_status {
+                debug!("Remote pin {} is {}", request_id, status.status);
+                last_status = status.status.clone();
+                on_status(&status);
+            }
+            if status.is_settled() {
+                return Ok(status);
+            }
+            if tokio::time::Instant::now() + interval > deadline {
+                return Err(anyhow!(
+                    "Timed out waiting for remote pin {} (last status: {})",
+                    request_id,
+                    status.status
+                ));
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn parse<T: serde::de::DeserializeOwned>(response: reqwest::Response) -> Result<T> {
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Pinning service error: {} - {}", status, body));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_server::{MockResponse, MockServer};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn pin_status(request_id: &str, cid: &str, status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "requestid": request_id,
+            "status": status,
+            "created": "2024-01-01T00:00:00Z",
+            "pin": { "cid": cid, "name": "my-planet" },
+            "delegates": []
+        })
+    }
+
+    #[tokio::test]
+    async fn test_pin_sends_bearer_token() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(202, pin_status("req-1", "bafy-new", "queued"))
+        })
+        .await;
+
+        let client = PinningClient::new(&server.url, "secret");
+        let status = client.pin("bafy-new", "my-planet").await.unwrap();
+        assert_eq!(status.requestid, "req-1");
+        assert_eq!(status.status, "queued");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/pins");
+        assert_eq!(requests[0].headers["authorization"], "Bearer secret");
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["cid"], "bafy-new");
+        assert_eq!(body["name"], "my-planet");
+    }
+
+    fn pin_list(results: Vec<serde_json::Value>) -> MockResponse {
+        MockResponse::json(
+            200,
+            serde_json::json!({ "count": results.len(), "results": results }),
+        )
+    }
+
+    fn created_at(mut status: serde_json::Value, created: &str) -> serde_json::Value {
+        status["created"] = created.into();
+        status
+    }
+
+    #[tokio::test]
+    async fn test_replace_pin_removes_previous_pins_once_pinned() {
+        let server = MockServer::start(|req| match (req.method.as_str(), req.path.as_str()) {
+            ("POST", _) => MockResponse::json(
+                202,
+                created_at(pin_status("req-new", "bafy-new", "queued"), "2024-01-02T00:00:00Z"),
+            ),
+            ("GET", "/pins/req-new") => MockResponse::json(
+                200,
+                created_at(pin_status("req-new", "bafy-new", "pinned"), "2024-01-02T00:00:00Z"),
+            ),
+            ("GET", _) => pin_list(vec![
+                created_at(pin_status("req-new", "bafy-new", "pinned"), "2024-01-02T00:00:00Z"),
+                pin_status("req-old", "bafy-old", "pinned"),
+                created_at(pin_status("req-newer", "bafy-newer", "queued"), "2024-01-03T00:00:00Z"),
+            ]),
+            _ => MockResponse::text(202, ""),
+        })
+        .await;
+
+        let client = PinningClient::new(&server.url, "secret");
+        let submitted = Mutex::new(None);
+        let status = client
+            .replace_pin(
+                "bafy-new",
+                "my-planet",
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+                |s| *submitted.lock().unwrap() = Some(s.status.clone()),
+                |_| {},
+            )
+            .await
+            .unwrap();
+        assert_eq!(status.status, "pinned");
+        assert_eq!(submitted.lock().unwrap().as_deref(), Some("queued"));
+
+        // 只删除更早的旧 pin，较晚提交的 pin 保留
+        let deleted: Vec<String> = server
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "DELETE")
+            .map(|r| r.path)
+            .collect();
+        assert_eq!(deleted, vec!["/pins/req-old".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_replace_pin_keeps_previous_pins_when_failed() {
+        let server = MockServer::start(|req| match req.method.as_str() {
+            "POST" => MockResponse::json(202, pin_status("req-new", "bafy-new", "queued")),
+            _ => MockResponse::json(200, pin_status("req-new", "bafy-new", "failed")),
+        })
+        .await;
+
+        let client = PinningClient::new(&server.url, "secret");
+        let status = client
+            .replace_pin(
+                "bafy-new",
+                "my-planet",
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+                |_| {},
+                |_| {},
+            )
+            .await
+            .unwrap();
+        assert_eq!(status.status, "failed");
+        assert!(server.requests().iter().all(|r| r.method != "DELETE"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_pin_polls_until_pinned() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let server = MockServer::start(move |_| {
+            let status = match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => "queued",
+                1 => "pinning",
+                _ => "pinned",
+            };
+            MockResponse::json(200, pin_status("req-1", "bafy-new", status))
+        })
+        .await;
+
+        let seen = Mutex::new(Vec::new());
+        let client = PinningClient::new(&server.url, "secret");
+        let status = client
+            .wait_for_pin(
+                "req-1",
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+                |s| seen.lock().unwrap().push(s.status.clone()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(status.status, "pinned");
+        assert_eq!(*seen.lock().unwrap(), vec!["queued", "pinning", "pinned"]);
+        assert_eq!(server.requests()[0].path, "/pins/req-1");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_pin_times_out() {
+        let server = MockServer::start(|_| {
+            MockResponse::json(200, pin_status("req-1", "bafy-new", "queued"))
+        })
+        .await;
+
+        let client = PinningClient::new(&server.url, "secret");
+        let err = client
+            .wait_for_pin(
+                "req-1",
+                Duration::from_millis(10),
+                Duration::from_millis(30),
+                |_| {},
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_api_error_is_reported() {
+        let server = MockServer::start(|_| MockResponse::text(401, "unauthorized")).await;
+
+        let client = PinningClient::new(&server.url, "bad-token");
+        let err = client.get_pin("req-1").await.unwrap_err();
+        assert!(err.to_string().contains("401"));
+    }
+}
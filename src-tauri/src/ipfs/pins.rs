@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tracing::debug;
+use uuid::Uuid;
+
+use super::daemon::IpfsClient;
+use crate::models::article::MyArticle;
+use crate::store::PlanetStoreHandle;
+
+// ============================================================
+// 本地 Pin 管理
+// 列出 / 添加 / 移除本地 pin，并把 CID 对应回拥有它的 Planet 或文章
+// ============================================================
+
+/// pin 的归属 — 序列化为 { "kind": "planet", ... }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PinOwner {
+    Planet {
+        planet_id: Uuid,
+        name: String,
+    },
+    Article {
+        planet_id: Uuid,
+        article_id: Uuid,
+        title: String,
+    },
+    FollowingPlanet {
+        planet_id: Uuid,
+        name: String,
+    },
+}
+
+/// ipfs_pin_ls / ipfs_pin_check 返回的单个 pin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsPinEntry {
+    pub cid: String,
+    pub pin_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<PinOwner>,
+}
+
+/// "ipfs:pin-progress" 事件的 payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsPinProgress {
+    pub cid: String,
+    /// 已拉取的块数
+    pub blocks: u64,
+    pub done: bool,
+}
+
+/// pin/add?progress=true 输出中的一行
+#[derive(Debug, Clone, PartialEq)]
+pub enum PinProgress {
+    Blocks(u64),
+    Done,
+}
+
+/// 解析 pin/add 的一行输出：{"Progress":n} 或最终的 {"Pins":[...]}
+pub fn parse_pin_progress(line: &[u8]) -> Result<Option<PinProgress>> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let value: serde_json::Value = serde_json::from_str(line)?;
+    if let Some(message) = value.get("Message").and_then(|m| m.as_str()) {
+        return Err(anyhow!("pin/add failed: {}", message));
+    }
+    if value.get("Pins").is_some() {
+        return Ok(Some(PinProgress::Done));
+    }
+    Ok(value
+        .get("Progress")
+        .and_then(|p| p.as_u64())
+        .map(PinProgress::Blocks))
+}
+
+/// 收集已知 CID 的归属：Planet 最近发布的根 CID、其下各文章目录、关注的 Planet
+/// 每个 Planet 只列一次根目录，文章目录的 CID 按目录名（文章 id）对应
+pub async fn collect_owners(
+    client: &IpfsClient,
+    store: &PlanetStoreHandle,
+    app: &AppHandle,
+) -> HashMap<String, PinOwner> {
+    let (my_planets, following) = match store.lock() {
+        Ok(s) => (s.my_planets.clone(), s.following_planets.clone()),
+        Err(_) => return HashMap::new(),
+    };
+
+    let mut owners = HashMap::new();
+    for planet in following {
+        if let Some(cid) = planet.cid {
+            owners.insert(
+                cid,
+                PinOwner::FollowingPlanet {
+                    planet_id: planet.id,
+                    name: planet.name,
+                },
+            );
+        }
+    }
+
+    for planet in my_planets {
+        let Some(root) = planet.last_published_cid.clone() else {
+            continue;
+        };
+        let entries = match client.list_directory(&root).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                debug!("Failed to list {}: {}", root, e);
+                HashMap::new()
+            }
+        };
+        let articles = MyArticle::load_all(&planet, app).unwrap_or_default();
+        for article in articles {
+            if let Some(cid) = entries.get(&article.id.to_string()) {
+                owners.insert(
+                    cid.clone(),
+                    PinOwner::Article {
+                        planet_id: planet.id,
+                        article_id: article.id,
+                        title: article.title,
+                    },
+                );
+            }
+        }
+        owners.insert(
+            root,
+            PinOwner::Planet {
+                planet_id: planet.id,
+                name: planet.name,
+            },
+        );
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pin_progress_lines() {
+        assert_eq!(
+            parse_pin_progress(b"{\"Progress\":42}\n").unwrap(),
+            Some(PinProgress::Blocks(42))
+        );
+        assert_eq!(
+            parse_pin_progress(b"{\"Pins\":[\"bafy\"]}").unwrap(),
+            Some(PinProgress::Done)
+        );
+        assert_eq!(parse_pin_progress(b"  \n").unwrap(), None);
+        assert!(parse_pin_progress(b"{\"Message\":\"context canceled\",\"Code\":0}").is_err());
+    }
+}
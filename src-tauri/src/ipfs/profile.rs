@@ -0,0 +1,335 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use tauri::AppHandle;
+use tracing::info;
+
+use super::command::KuboCommand;
+use crate::helpers::paths;
+
+// ============================================================
+// 网络配置方案（Network Profile）
+// 控制连接数、Peering、DoH、Reprovider、加速 DHT 与中继
+// 应用时读取完整配置，合并后通过 `ipfs config replace` 一次写入，
+// 不会出现只改了一半的配置；修改在 daemon 下次启动时生效
+// ============================================================
+
+pub const LOW_POWER: &str = "low-power";
+pub const DEFAULT: &str = "default";
+pub const SERVER: &str = "server";
+
+/// API 允许的跨域来源与方法（不随方案变化）
+const CORS_ALLOW_ORIGIN: &[&str] = &["https://webui.ipfs.io"];
+const CORS_ALLOW_METHODS: &[&str] = &["PUT", "POST"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    pub name: String,
+    /// Swarm.ConnMgr
+    pub conn_mgr: ConnMgrWatermarks,
+    /// Peering.Peers
+    #[serde(default)]
+    pub peering_peers: Vec<PeeringPeer>,
+    /// DNS.Resolvers：域名后缀 → DoH 地址
+    #[serde(default)]
+    pub dns_resolvers: BTreeMap<String, String>,
+    /// Reprovider.Strategy
+    #[serde(default)]
+    pub reprovider_strategy: ReproviderStrategy,
+    /// Routing.AcceleratedDHTClient
+    #[serde(default)]
+    pub accelerated_dht: bool,
+    /// Swarm.RelayClient.Enabled
+    #[serde(default = "default_true")]
+    pub relay_client: bool,
+    /// Swarm.RelayService.Enabled
+    #[serde(default)]
+    pub relay_service: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnMgrWatermarks {
+    pub low_water: u32,
+    pub high_water: u32,
+    pub grace_period_secs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeeringPeer {
+    pub id: String,
+    #[serde(default)]
+    pub addrs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReproviderStrategy {
+    #[default]
+    All,
+    Pinned,
+    Roots,
+}
+
+impl ReproviderStrategy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Pinned => "pinned",
+            Self::Roots => "roots",
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 内置方案；default 与早期版本写死的配置一致
+pub fn builtin_profiles() -> Vec<NetworkProfile> {
+    vec![
+        NetworkProfile {
+            name: LOW_POWER.into(),
+            conn_mgr: ConnMgrWatermarks {
+                low_water: 5,
+                high_water: 10,
+                grace_period_secs: 20,
+            },
+            peering_peers: default_peers(),
+            dns_resolvers: default_resolvers(),
+            reprovider_strategy: ReproviderStrategy::Roots,
+            accelerated_dht: false,
+            relay_client: true,
+            relay_service: false,
+        },
+        NetworkProfile {
+            name: DEFAULT.into(),
+            conn_mgr: ConnMgrWatermarks {
+                low_water: 10,
+                high_water: 20,
+                grace_period_secs: 20,
+            },
+            peering_peers: default_peers(),
+            dns_resolvers: default_resolvers(),
+            reprovider_strategy: ReproviderStrategy::All,
+            accelerated_dht: false,
+            relay_client: true,
+            relay_service: false,
+        },
+        NetworkProfile {
+            name: SERVER.into(),
+            conn_mgr: ConnMgrWatermarks {
+                low_water: 100,
+                high_water: 400,
+                grace_period_secs: 60,
+            },
+            peering_peers: default_peers(),
+            dns_resolvers: default_resolvers(),
+            reprovider_strategy: ReproviderStrategy::All,
+            accelerated_dht: true,
+            relay_client: false,
+            relay_service: true,
+        },
+    ]
+}
+
+/// Planet 的 Peering 节点（与原项目一致）
+fn default_peers() -> Vec<PeeringPeer> {
+    let peer = |id: &str, addrs: &[&str]| PeeringPeer {
+        id: id.into(),
+        addrs: addrs.iter().map(|a| a.to_string()).collect(),
+    };
+    vec![
+        peer(
+            "12D3KooWBJY6ZVV8Tk8UDDFMEqWoxn89Xc8wnpm8uBFSR3ijDkui",
+            &[
+                "/ip4/167.71.172.216/tcp/4001",
+                "/ip6/2604:a880:800:10::826:1/tcp/4001",
+                "/ip4/167.71.172.216/udp/4001/quic",
+                "/ip6/2604:a880:800:10::826:1/udp/4001/quic",
+            ],
+        ),
+        peer(
+            "12D3KooWDaGQ3Fu3iLgFxrrg5Vfef9z5L3DQZoyqFxQJbKKPnCc8",
+            &[
+                "/ip4/143.198.18.166/tcp/4001",
+                "/ip6/2604:a880:800:10::735:7001/tcp/4001",
+                "/ip4/143.198.18.166/udp/4001/quic",
+                "/ip6/2604:a880:800:10::735:7001/udp/4001/quic",
+            ],
+        ),
+        peer(
+            "12D3KooWJ6MTkNM8Bu8DzNiRm1GY3Wqh8U8Pp1zRWap6xY3MvsNw",
+            &["/dnsaddr/node-1.ipfs.bit.site"],
+        ),
+        peer(
+            "12D3KooWQ85aSCFwFkByr5e3pUCQeuheVhobVxGSSs1DrRQHGv1t",
+            &["/dnsaddr/node-1.ipfs.4everland.net"],
+        ),
+        peer(
+            "12D3KooWGtYkBAaqJMJEmywMxaCiNP7LCEFUAFiLEBASe232c2VH",
+            &["/dns4/bitswap.filebase.io/tcp/443/wss"],
+        ),
+    ]
+}
+
+/// 各 Web3 域名后缀的 DNS over HTTPS 解析器
+fn default_resolvers() -> BTreeMap<String, String> {
+    [
+        ("bit.", "https://dweb-dns.v2ex.pro/dns-query"),
+        ("sol.", "https://dweb-dns.v2ex.pro/dns-query"),
+        ("fc.", "https://dweb-dns.v2ex.pro/dns-query"),
+        ("eth.", "https://dns.eth.limo/dns-query"),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+}
+
+impl NetworkProfile {
+    pub fn validate(&self) -> Result<()> {
+        let name = &self.name;
+        if name.trim().is_empty() {
+            return Err(anyhow!("Network profile name cannot be empty"));
+        }
+        let conn = &self.conn_mgr;
+        if conn.high_water == 0 || conn.low_water > conn.high_water {
+            return Err(anyhow!(
+                "Network profile {}: connection low water ({}) must not exceed high water ({}), and high water must be positive",
+                name,
+                conn.low_water,
+                conn.high_water
+            ));
+        }
+        let mut ids = HashSet::new();
+        for peer in &self.peering_peers {
+            if peer.id.trim().is_empty() || !ids.insert(peer.id.as_str()) {
+                return Err(anyhow!("Network profile {}: peering peer IDs must be unique and non-empty", name));
+            }
+            if let Some(addr) = peer.addrs.iter().find(|a| !a.starts_with('/')) {
+                return Err(anyhow!("Network profile {}: {:?} is not a multiaddr", name, addr));
+            }
+        }
+        for (domain, url) in &self.dns_resolvers {
+            if domain != "." && !domain.ends_with('.') {
+                return Err(anyhow!("Network profile {}: resolver domain {:?} must end with '.'", name, domain));
+            }
+            if !url.starts_with("https://") {
+                return Err(anyhow!("Network profile {}: resolver {:?} must be an https:// DoH URL", name, url));
+            }
+        }
+        Ok(())
+    }
+
+    /// 将方案合并进完整的 Kubo 配置，其余字段保持不变
+    pub fn merge_into(&self, config: &mut Value) -> Result<()> {
+        let peers: Vec<Value> = self
+            .peering_peers
+            .iter()
+            .map(|p| json!({ "ID": p.id, "Addrs": p.addrs }))
+            .collect();
+        let entries = [
+            (
+                "Swarm.ConnMgr",
+                json!({
+                    "Type": "basic",
+                    "LowWater": self.conn_mgr.low_water,
+                    "HighWater": self.conn_mgr.high_water,
+                    "GracePeriod": format!("{}s", self.conn_mgr.grace_period_secs),
+                }),
+            ),
+            ("Peering.Peers", json!(peers)),
+            ("DNS.Resolvers", json!(self.dns_resolvers)),
+            ("Reprovider.Strategy", json!(self.reprovider_strategy.as_str())),
+            ("Routing.AcceleratedDHTClient", json!(self.accelerated_dht)),
+            ("Swarm.RelayClient.Enabled", json!(self.relay_client)),
+            ("Swarm.RelayService.Enabled", json!(self.relay_service)),
+            ("API.HTTPHeaders.Access-Control-Allow-Origin", json!(CORS_ALLOW_ORIGIN)),
+            ("API.HTTPHeaders.Access-Control-Allow-Methods", json!(CORS_ALLOW_METHODS)),
+        ];
+        for (key, value) in entries {
+            set_path(config, key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// 按 "A.B.C" 路径写入，缺失或为 null 的中间对象会被创建
+fn set_path(config: &mut Value, key: &str, value: Value) -> Result<()> {
+    let (parents, last) = match key.rsplit_once('.') {
+        Some((parents, last)) => (parents.split('.').collect::<Vec<_>>(), last),
+        None => (Vec::new(), key),
+    };
+    let mut node = config;
+    for part in parents {
+        if node.get(part).is_none_or(Value::is_null) {
+            node[part] = json!({});
+        }
+        node = &mut node[part];
+    }
+    node.as_object_mut()
+        .ok_or_else(|| anyhow!("Kubo config field {} is not an object", key))?
+        .insert(last.to_string(), value);
+    Ok(())
+}
+
+/// 校验并应用方案到本地仓库
+pub fn apply(app: &AppHandle, profile: &NetworkProfile) -> Result<()> {
+    profile.validate()?;
+    let output = KuboCommand::config_show(app.clone()).run()?;
+    if output.ret != 0 {
+        return Err(anyhow!("Failed to read Kubo config: {}", output.stderr.trim()));
+    }
+    let mut config: Value = serde_json::from_str(&output.stdout)?;
+    if !config.is_object() {
+        return Err(anyhow!("Kubo config is not a JSON object"));
+    }
+    profile.merge_into(&mut config)?;
+
+    let file = paths::get_temp_path(app).join(format!("kubo-config-{}.json", uuid::Uuid::new_v4()));
+    fs::write(&file, serde_json::to_vec_pretty(&config)?)?;
+    let result = KuboCommand::config_replace(app.clone(), &file.to_string_lossy()).run();
+    let _ = fs::remove_file(&file);
+    let output = result?;
+    if output.ret != 0 {
+        return Err(anyhow!("Failed to apply network profile {}: {}", profile.name, output.stderr.trim()));
+    }
+    info!("Applied network profile {}", profile.name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_keeps_other_config_fields() {
+        for profile in builtin_profiles() {
+            profile.validate().unwrap();
+        }
+        let server = builtin_profiles().into_iter().find(|p| p.name == SERVER).unwrap();
+        let mut config = json!({
+            "Identity": { "PeerID": "12D3KooWTest" },
+            "Swarm": { "ConnMgr": {}, "RelayClient": null, "DisableNatPortMap": true },
+            "Peering": null,
+        });
+        server.merge_into(&mut config).unwrap();
+
+        assert_eq!(config["Identity"]["PeerID"], "12D3KooWTest");
+        assert_eq!(config["Swarm"]["DisableNatPortMap"], true);
+        assert_eq!(config["Swarm"]["ConnMgr"]["HighWater"], 400);
+        assert_eq!(config["Swarm"]["ConnMgr"]["GracePeriod"], "60s");
+        assert_eq!(config["Swarm"]["RelayService"]["Enabled"], true);
+        assert_eq!(config["Routing"]["AcceleratedDHTClient"], true);
+        assert_eq!(config["Peering"]["Peers"].as_array().unwrap().len(), 5);
+        assert_eq!(config["DNS"]["Resolvers"]["eth."], "https://dns.eth.limo/dns-query");
+
+        let mut invalid = server.clone();
+        invalid.conn_mgr.low_water = 500;
+        assert!(invalid.validate().is_err());
+        let mut invalid = server;
+        invalid.dns_resolvers.insert("eth".into(), "http://example.com".into());
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use super::command::KuboCommand;
+use crate::models::settings::AppSettings;
+
+// ============================================================
+// 仓库维护：残留锁检测、配置校验、备份与修复
+// 应用崩溃后 repo.lock 和 api 文件会留在仓库中，导致下次启动失败
+// ============================================================
+
+/// 探测 api 文件所指地址时的超时
+const API_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 从 api 文件的 multiaddr（如 /ip4/127.0.0.1/tcp/5981）解析出 host:port
+pub fn api_address(multiaddr: &str) -> Option<String> {
+    let parts: Vec<&str> = multiaddr.trim().split('/').collect();
+    match parts.as_slice() {
+        ["", "ip4", host, "tcp", port, ..] => Some(format!("{}:{}", host, port)),
+        ["", "ip6", host, "tcp", port, ..] => Some(format!("[{}]:{}", host, port)),
+        ["", "dns" | "dns4" | "dns6", host, "tcp", port, ..] => Some(format!("{}:{}", host, port)),
+        _ => None,
+    }
+}
+
+/// 检查并清理残留的 api / repo.lock 文件
+/// 仍有存活的 Kubo 使用该仓库时返回错误
+pub async fn ensure_unlocked(app: &AppHandle, repo_path: &Path) -> Result<()> {
+    let api_file = repo_path.join("api");
+    if let Ok(content) = fs::read_to_string(&api_file) {
+        if let Some(address) = api_address(&content) {
+            if probe_api(&address).await {
+                return Err(anyhow!(
+                    "The IPFS repo at {} is in use by another running Kubo (API at {}). \
+                     Stop that process and try again.",
+                    repo_path.display(),
+                    address
+                ));
+            }
+        }
+        warn!("Removing stale api file ({})", content.trim());
+        fs::remove_file(&api_file)?;
+    }
+
+    let lock_file = repo_path.join("repo.lock");
+    if lock_file.exists() {
+        // 由 Kubo 自己判断锁是否仍被持有：获取锁失败说明有存活进程
+        let output = KuboCommand::repo_stat(app.clone()).run_async().await?;
+        if output.ret != 0 && output.stderr.contains("someone else has the lock") {
+            return Err(anyhow!(
+                "The IPFS repo at {} is locked by another running Kubo process. \
+                 Stop that process and try again.",
+                repo_path.display()
+            ));
+        }
+        if output.ret == 0 {
+            // Kubo 正常退出时会删除 repo.lock，这里只是崩溃遗留的文件
+            if lock_file.exists() {
+                warn!("Removing stale repo.lock");
+                fs::remove_file(&lock_file)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn probe_api(address: &str) -> bool {
+    let Ok(client) = Client::builder().timeout(API_PROBE_TIMEOUT).build() else {
+        return false;
+    };
+    client
+        .post(format!("http://{}/api/v0/id", address))
+        .send()
+        .await
+        .is_ok_and(|r| r.status().is_success())
+}
+
+/// 校验仓库配置文件能否解析
+pub fn check_config(repo_path: &Path) -> Result<()> {
+    let content = fs::read_to_string(repo_path.join("config"))
+        .map_err(|e| anyhow!("IPFS repo config is missing ({}). Run repair to re-initialise the repo.", e))?;
+    serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|e| anyhow!("IPFS repo config is corrupt ({}). Run repair to re-initialise the repo.", e))?;
+    Ok(())
+}
+
+/// 将仓库完整复制到同级的 ipfs-backup-{label}-{时间} 目录
+pub fn backup(repo_path: &Path, label: &str) -> Result<PathBuf> {
+    let name = format!(
+        "ipfs-backup-{}-{}",
+        label,
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    let target = repo_path.with_file_name(name);
+    copy_dir(repo_path, &target)?;
+    info!("Backed up IPFS repo to {:?}", target);
+    Ok(target)
+}
+
+/// 修复仓库：备份后重新 init，并恢复 keystore（Planet 的 IPNS 密钥）
+/// 返回备份目录；节点 PeerID 会随之改变
+pub fn repair(app: &AppHandle, repo_path: &Path) -> Result<PathBuf> {
+    let backup_dir = backup(repo_path, "repair")?;
+
+    fs::remove_dir_all(repo_path)?;
+    fs::create_dir_all(repo_path)?;
+    let output = KuboCommand::ipfs_init(app.clone()).run()?;
+    if output.ret != 0 {
+        return Err(anyhow!(
+            "Failed to re-initialise IPFS repo: {}. A backup is at {}",
+            output.stderr.trim(),
+            backup_dir.display()
+        ));
+    }
+
+    let keystore = backup_dir.join("keystore");
+    if keystore.is_dir() {
+        copy_dir(&keystore, &repo_path.join("keystore"))?;
+        info!("Restored keystore from backup");
+    }
+
+    // 新仓库使用 Kubo 默认端口，需要让下次 setup 重新写入端口配置
+    if let Err(e) = AppSettings::update(app, |s| {
+        s.swarm_port.last_used = None;
+        s.api_port.last_used = None;
+        s.gateway_port.last_used = None;
+    }) {
+        warn!("Failed to reset saved daemon ports: {}", e);
+    }
+
+    info!("IPFS repo repaired");
+    Ok(backup_dir)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_address_from_multiaddr() {
+        assert_eq!(
+            api_address("/ip4/127.0.0.1/tcp/5981\n").as_deref(),
+            Some("127.0.0.1:5981")
+        );
+        assert_eq!(api_address("/ip6/::1/tcp/5001").as_deref(), Some("[::1]:5001"));
+        assert_eq!(api_address("/unix/tmp/ipfs.sock"), None);
+    }
+}
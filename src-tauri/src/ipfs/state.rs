@@ -1,54 +1,105 @@
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Manager, Emitter};
 use tokio::sync::Mutex;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
-use super::daemon::IpfsDaemon;
+use super::backend::IpfsBackend;
+use super::keys;
+use super::daemon::{IpfsClient, IpfsDaemon};
+use super::lifecycle::DaemonLifecycle;
 use super::models::{IpfsBandwidth, IpfsStateSnapshot, ServerInfo};
+use crate::store::PlanetStoreHandle;
 
 /// IPFS 全局状态
 /// 对应原项目 IPFSState.swift
 ///
 /// 在 Tauri 中通过 app.manage(Arc<Mutex<IpfsState>>) 注入全局状态
+/// setup / launch 期间会长时间持有该锁，状态查询应读取 IpfsStatusHandle
 pub struct IpfsState {
     pub daemon: IpfsDaemon,
-    pub online: bool,
-    pub is_operating: bool,
+    pub lifecycle: DaemonLifecycle,
     pub repo_size: Option<i64>,
+    pub storage_max: Option<i64>,
     pub server_info: Option<ServerInfo>,
-    pub error_message: Option<String>,
+    status: IpfsStatusHandle,
 }
 
+/// 最近一次推送的状态快照，读取时无需等待 IPFS 状态锁
+pub type IpfsStatusHandle = Arc<RwLock<IpfsStateSnapshot>>;
+
 impl IpfsState {
     pub fn new(app: AppHandle) -> Self {
-        Self {
+        let mut state = Self {
             daemon: IpfsDaemon::new(app),
-            online: false,
-            is_operating: false,
+            lifecycle: DaemonLifecycle::default(),
             repo_size: None,
+            storage_max: None,
             server_info: None,
-            error_message: None,
+            status: Arc::default(),
+        };
+        state.status = Arc::new(RwLock::new(state.snapshot()));
+        state
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.lifecycle.is_online()
+    }
+
+    /// 状态快照的共享句柄
+    pub fn status(&self) -> IpfsStatusHandle {
+        self.status.clone()
+    }
+
+    /// 校验并切换生命周期，随后推送状态
+    pub fn transition(&mut self, to: DaemonLifecycle, app: &AppHandle) -> Result<()> {
+        if !self.lifecycle.can_transition(&to) {
+            return Err(anyhow!(
+                "Cannot change IPFS state from {} to {}",
+                self.lifecycle,
+                to
+            ));
         }
+        debug!("IPFS lifecycle: {} -> {}", self.lifecycle, to);
+        self.lifecycle = to;
+        self.emit_state_changed(app);
+        Ok(())
     }
 
     /// 生成前端可用的状态快照
     pub fn snapshot(&self) -> IpfsStateSnapshot {
         IpfsStateSnapshot {
-            online: self.online,
-            is_operating: self.is_operating,
+            online: self.lifecycle.is_online(),
+            is_operating: self.lifecycle.is_busy(),
+            lifecycle: self.lifecycle.clone(),
             api_port: self.daemon.api_port.unwrap_or(5981),
             gateway_port: self.daemon.gateway_port.unwrap_or(18181),
             swarm_port: self.daemon.swarm_port.unwrap_or(4001),
             repo_size: self.repo_size,
+            storage_max: self.storage_max,
+            managed: self.daemon.is_managed(),
+            gateway_url: self.daemon.external_gateway(),
             server_info: self.server_info.clone(),
-            error_message: self.error_message.clone(),
+            error_message: self.lifecycle.error_message().map(str::to_string),
+        }
+    }
+
+    /// 刷新仓库用量与容量上限
+    pub async fn refresh_repo_state(&mut self) {
+        if let Ok(repo) = self.daemon.client().repo_state().await {
+            self.repo_size = Some(repo.repo_size);
+            self.storage_max = Some(repo.storage_max);
         }
     }
 
-    /// 发送状态变化事件到前端
+    /// 更新共享快照并发送状态变化事件到前端
     pub fn emit_state_changed(&self, app: &AppHandle) {
         let snapshot = self.snapshot();
+        if let Ok(mut status) = self.status.write() {
+            *status = snapshot.clone();
+        }
         if let Err(e) = app.emit("ipfs:state-changed", &snapshot) {
             error!("Failed to emit ipfs state: {}", e);
         }
@@ -58,6 +109,16 @@ impl IpfsState {
 /// 类型别名，方便在 Tauri State 中使用
 pub type IpfsStateHandle = Arc<Mutex<IpfsState>>;
 
+/// daemon 在线时取得其轻量句柄，随即释放 IPFS 锁
+/// 发布、pin、CAR 导入导出等耗时操作应在锁外使用该句柄，避免阻塞状态查询和 shutdown
+pub async fn online_client(state: &IpfsStateHandle) -> Result<IpfsClient> {
+    let s = state.lock().await;
+    if !s.is_online() {
+        return Err(anyhow!("IPFS daemon is not online"));
+    }
+    Ok(s.daemon.client())
+}
+
 // ============================================================
 // 生命周期管理函数
 // 供 main.rs 和 commands/ipfs.rs 调用
@@ -67,75 +128,107 @@ pub type IpfsStateHandle = Arc<Mutex<IpfsState>>;
 /// 对应 Swift: IPFSState.init() 中的 Task
 pub async fn auto_start(state: IpfsStateHandle, app: AppHandle) {
     info!("Auto-starting IPFS daemon...");
+    match start(&state, &app, true).await {
+        Ok(()) => info!("IPFS auto-start completed successfully"),
+        Err(e) => error!("IPFS auto-start failed: {}", e),
+    }
+}
 
-    // 标记为操作中
-    {
-        let mut s = state.lock().await;
-        s.is_operating = true;
-        s.emit_state_changed(&app);
+/// （可选 setup 后）launch daemon，失败时进入 Failed
+/// 整个过程持有 IPFS 状态锁，进度通过 IpfsStatusHandle 与事件对外可见
+pub async fn start(state: &IpfsStateHandle, app: &AppHandle, setup: bool) -> Result<()> {
+    let mut s = state.lock().await;
+    if setup {
+        s.transition(DaemonLifecycle::SettingUp, app)?;
+        if let Err(e) = s.daemon.setup().await {
+            let message = format!("Setup failed: {}", e);
+            s.transition(DaemonLifecycle::Failed { message: message.clone() }, app)?;
+            return Err(anyhow!(message));
+        }
     }
 
-    // Setup
-    let setup_result = {
-        let mut s = state.lock().await;
-        s.daemon.setup().await
-    };
+    s.transition(DaemonLifecycle::Starting, app)?;
+    if let Err(e) = s.daemon.launch().await {
+        let message = format!("Launch failed: {}", e);
+        s.transition(DaemonLifecycle::Failed { message: message.clone() }, app)?;
+        return Err(anyhow!(message));
+    }
 
-    if let Err(e) = setup_result {
-        error!("IPFS setup failed: {}", e);
-        let mut s = state.lock().await;
-        s.is_operating = false;
-        s.error_message = Some(format!("IPFS setup failed: {}", e));
-        s.emit_state_changed(&app);
-        return;
+    if let Ok(info) = s.daemon.get_server_info().await {
+        s.server_info = Some(info);
     }
+    s.refresh_repo_state().await;
+    s.transition(DaemonLifecycle::Online, app)?;
+    ensure_planet_keys(&s.daemon.client(), app).await;
+    Ok(())
+}
 
-    // Launch
-    let launch_result = {
-        let mut s = state.lock().await;
-        s.daemon.launch().await
+/// daemon 上线后为离线期间创建的 Planet 补生成 IPNS key，并移除已删除 Planet 残留的 key
+/// key 操作期间不持有 store 锁，完成后在锁内写回 store 中的 Planet 并保存
+pub async fn ensure_planet_keys<B: IpfsBackend>(backend: &B, app: &AppHandle) {
+    let Some(store) = app.try_state::<PlanetStoreHandle>() else {
+        return;
+    };
+    let (pending, in_use) = match store.lock() {
+        Ok(store) => (
+            store.planets_without_keys(),
+            store
+                .my_planets
+                .iter()
+                .map(|p| p.key_name())
+                .collect::<HashSet<_>>(),
+        ),
+        Err(e) => {
+            error!("Failed to lock planet store: {}", e);
+            return;
+        }
     };
+    keys::remove_orphans(backend, app, &in_use).await;
 
-    match launch_result {
-        Ok(()) => {
-            let mut s = state.lock().await;
-            s.online = true;
-            s.is_operating = false;
-            s.error_message = None;
-
-            // 获取 server info
-            if let Ok(info) = s.daemon.get_server_info().await {
-                s.server_info = Some(info);
-            }
-
-            // 获取 repo size
-            if let Ok(size) = s.daemon.get_repo_size().await {
-                s.repo_size = Some(size);
-            }
-
-            s.emit_state_changed(&app);
-            info!("IPFS auto-start completed successfully");
+    let mut generated = Vec::new();
+    for planet in pending {
+        match planet.ipns_key_id(backend).await {
+            Ok(key_id) => generated.push((planet.id, key_id)),
+            Err(e) => error!("Failed to ensure IPNS key for planet {}: {}", planet.id, e),
         }
-        Err(e) => {
-            error!("IPFS launch failed: {}", e);
-            let mut s = state.lock().await;
-            s.online = false;
-            s.is_operating = false;
-            s.error_message = Some(format!("IPFS launch failed: {}", e));
-            s.emit_state_changed(&app);
+    }
+    if generated.is_empty() {
+        return;
+    }
+
+    let Ok(mut store) = store.lock() else {
+        return;
+    };
+    for (planet_id, key_id) in generated {
+        // 期间被删除或已有 key 的 Planet 不再写回
+        let Some(planet) = store.get_planet_mut(planet_id).filter(|p| !p.has_ipns_key()) else {
+            continue;
+        };
+        planet.ipns = key_id;
+        match planet.save(app) {
+            Ok(()) => info!("Generated IPNS key for planet {}: {}", planet_id, planet.ipns),
+            Err(e) => error!("Failed to save IPNS key for planet {}: {}", planet_id, e),
         }
     }
+    store.emit_state_changed(app);
 }
 
 /// 应用退出时优雅关闭 daemon
 /// 对应 Swift: PlanetStatusManager.terminate() 中 IPFSDaemon.shared.shutdown()
-pub async fn graceful_shutdown(state: IpfsStateHandle) {
-    info!("Graceful shutdown: stopping IPFS daemon...");
+pub async fn graceful_shutdown(state: IpfsStateHandle, app: &AppHandle) {
     let mut s = state.lock().await;
+    if !s.daemon.is_managed() {
+        info!("Graceful shutdown: external IPFS node is left running");
+        return;
+    }
+    info!("Graceful shutdown: stopping IPFS daemon...");
+    // 未上线时同样尝试关闭，清理可能残留的子进程
+    let stopping = s.transition(DaemonLifecycle::Stopping, app).is_ok();
     if let Err(e) = s.daemon.shutdown().await {
         error!("Failed to shutdown IPFS daemon: {}", e);
     }
-    s.online = false;
-    s.is_operating = false;
+    if stopping {
+        let _ = s.transition(DaemonLifecycle::Offline, app);
+    }
     info!("IPFS daemon stopped");
 }
@@ -0,0 +1,185 @@
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tracing::{error, info, warn};
+
+use super::lifecycle::DaemonLifecycle;
+use super::models::IpfsOfflineEvent;
+use super::state::{ensure_planet_keys, IpfsStateHandle};
+
+// ============================================================
+// Daemon 崩溃监控
+// 定期检查 daemon 子进程是否退出、API 是否响应
+// 发现异常后标记离线、通知前端，并按指数退避自动重启
+// ============================================================
+
+/// 检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 连续多少次健康检查失败后视为崩溃
+const MAX_HEALTH_FAILURES: u32 = 3;
+
+/// 最多自动重启次数
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// 第一次重启前的等待时间，之后每次翻倍
+const RESTART_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// 重启等待时间上限
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// 在 main.rs setup 中调用，启动后台监控任务
+pub fn start(state: IpfsStateHandle, app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut health_failures = 0;
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            if let Some(event) = check(&state, &mut health_failures).await {
+                health_failures = 0;
+                handle_crash(&state, &app, event).await;
+            }
+        }
+    });
+}
+
+/// 检查一次 daemon 状态，发现崩溃时返回离线事件
+async fn check(state: &IpfsStateHandle, health_failures: &mut u32) -> Option<IpfsOfflineEvent> {
+    let mut s = state.lock().await;
+    // 未上线或正在 setup/launch/shutdown 时不干预
+    if !s.is_online() {
+        *health_failures = 0;
+        return None;
+    }
+
+    if let Some(status) = s.daemon.try_wait_exit() {
+        return Some(IpfsOfflineEvent {
+            reason: "exited".into(),
+            exit_code: status.code(),
+            stderr_tail: s.daemon.stderr_tail(),
+        });
+    }
+
+    if s.daemon.check_online().await {
+        *health_failures = 0;
+        return None;
+    }
+    *health_failures += 1;
+    warn!(
+        "IPFS health check failed ({}/{})",
+        health_failures, MAX_HEALTH_FAILURES
+    );
+    if *health_failures < MAX_HEALTH_FAILURES {
+        return None;
+    }
+    Some(IpfsOfflineEvent {
+        reason: "health_check_failed".into(),
+        exit_code: None,
+        stderr_tail: s.daemon.stderr_tail(),
+    })
+}
+
+/// 标记离线并尝试重启
+async fn handle_crash(state: &IpfsStateHandle, app: &AppHandle, event: IpfsOfflineEvent) {
+    let message = describe(&event);
+    error!("{}", message);
+    {
+        let mut s = state.lock().await;
+        let failed = DaemonLifecycle::Failed {
+            message: message.clone(),
+        };
+        // 检查之后状态已被手动改变（例如用户关闭了 daemon）
+        if let Err(e) = s.transition(failed, app) {
+            warn!("Skipping IPFS restart: {}", e);
+            return;
+        }
+    }
+    if let Err(e) = app.emit("ipfs:offline", &event) {
+        error!("Failed to emit ipfs offline event: {}", e);
+    }
+
+    for attempt in 1..=MAX_RESTART_ATTEMPTS {
+        let delay = restart_delay(attempt);
+        info!(
+            "Restarting IPFS daemon in {}s (attempt {}/{})",
+            delay.as_secs(),
+            attempt,
+            MAX_RESTART_ATTEMPTS
+        );
+        tokio::time::sleep(delay).await;
+
+        let mut s = state.lock().await;
+        // 期间已被手动启动、关闭或正在进行其他操作
+        if s.transition(DaemonLifecycle::Starting, app).is_err() {
+            return;
+        }
+
+        match s.daemon.launch().await {
+            Ok(()) => {
+                if let Ok(info) = s.daemon.get_server_info().await {
+                    s.server_info = Some(info);
+                }
+                let _ = s.transition(DaemonLifecycle::Online, app);
+                ensure_planet_keys(&s.daemon.client(), app).await;
+                info!("IPFS daemon restarted after {} attempt(s)", attempt);
+                return;
+            }
+            Err(e) => {
+                warn!("IPFS restart attempt {} failed: {}", attempt, e);
+                let message = format!(
+                    "{} (restart attempt {}/{} failed: {})",
+                    message, attempt, MAX_RESTART_ATTEMPTS, e
+                );
+                let _ = s.transition(DaemonLifecycle::Failed { message }, app);
+            }
+        }
+    }
+
+    error!(
+        "IPFS daemon could not be restarted after {} attempts",
+        MAX_RESTART_ATTEMPTS
+    );
+}
+
+/// 第 attempt 次重启前的等待时间
+pub fn restart_delay(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    RESTART_BASE_DELAY.saturating_mul(factor).min(RESTART_MAX_DELAY)
+}
+
+/// 生成写入 error_message 的描述：退出码 + stderr 末尾
+pub fn describe(event: &IpfsOfflineEvent) -> String {
+    let mut message = match (event.reason.as_str(), event.exit_code) {
+        ("exited", Some(code)) => format!("IPFS daemon exited unexpectedly (code {})", code),
+        ("exited", None) => "IPFS daemon was terminated by a signal".to_string(),
+        _ => "IPFS daemon stopped responding".to_string(),
+    };
+    if !event.stderr_tail.is_empty() {
+        message.push_str(":\n");
+        message.push_str(&event.stderr_tail.join("\n"));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_delay_backs_off() {
+        assert_eq!(restart_delay(1), Duration::from_secs(2));
+        assert_eq!(restart_delay(2), Duration::from_secs(4));
+        assert_eq!(restart_delay(5), Duration::from_secs(32));
+        assert_eq!(restart_delay(10), RESTART_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_describe_includes_exit_code_and_stderr() {
+        let event = IpfsOfflineEvent {
+            reason: "exited".into(),
+            exit_code: Some(1),
+            stderr_tail: vec!["Error: repo is locked".into()],
+        };
+        let message = describe(&event);
+        assert!(message.contains("code 1"));
+        assert!(message.ends_with("Error: repo is locked"));
+    }
+}
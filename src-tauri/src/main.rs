@@ -10,13 +10,16 @@ mod models;
 mod store;
 mod template;
 mod keystore;
+mod publish;
 
 use std::sync::{Arc, Mutex};
 use tracing::info;
 use tracing_subscriber;
 use tauri::Manager;
 
+use ipfs::metrics::{MetricsHandle, MetricsHistory};
 use ipfs::state::{IpfsState, IpfsStateHandle};
+use models::settings::{AppSettings, SettingsHandle};
 use store::{PlanetStore, PlanetStoreHandle};
 
 fn main() {
@@ -33,6 +36,8 @@ fn main() {
             // Phase 0
             commands::app::get_kubo_path,
             commands::app::hello_world,
+            commands::settings::settings_get,
+            commands::settings::settings_update,
             // Phase 1 — IPFS
             commands::ipfs::ipfs_get_state,
             commands::ipfs::ipfs_setup,
@@ -40,6 +45,20 @@ fn main() {
             commands::ipfs::ipfs_shutdown,
             commands::ipfs::ipfs_gc,
             commands::ipfs::ipfs_refresh_status,
+            commands::ipfs::ipfs_repo_stat,
+            commands::ipfs::ipfs_repair_repo,
+            commands::ipfs::ipfs_set_network_profile,
+            commands::ipfs::ipfs_get_logs,
+            commands::ipfs::ipfs_save_logs,
+            commands::ipfs::ipfs_get_metrics,
+            commands::ipfs::ipfs_swarm_peers,
+            commands::ipfs::ipfs_swarm_connect,
+            commands::ipfs::ipfs_swarm_disconnect,
+            commands::ipfs::ipfs_peering_peers,
+            commands::ipfs::ipfs_pin_ls,
+            commands::ipfs::ipfs_pin_add,
+            commands::ipfs::ipfs_pin_rm,
+            commands::ipfs::ipfs_pin_check,
             // Phase 2: Planet Commands ← 新增
             commands::planet::planet_get_state,
             commands::planet::planet_list,
@@ -47,6 +66,11 @@ fn main() {
             commands::planet::planet_get,
             commands::planet::planet_update,
             commands::planet::planet_delete,
+            // Phase 3: Publish
+            commands::planet::planet_publish,
+            commands::planet::planet_export_car,
+            commands::planet::planet_import_car,
+            commands::planet::planet_filebase_status,
             // Phase 2: Article Commands ← 新增
             commands::article::article_list,
             commands::article::article_create,
@@ -72,12 +96,43 @@ fn main() {
                 tracing::error!("Failed to load planets: {}", e);
             }
             let planet_store_handle: PlanetStoreHandle = Arc::new(Mutex::new(planet_store));
+            let settings_handle: SettingsHandle = Arc::new(Mutex::new(AppSettings::load(&app_handle)));
+
+            // daemon 日志缓冲单独注入，读取时无需等待 IPFS 状态锁
+            let daemon_logs = ipfs_state
+                .try_lock()
+                .expect("IPFS state is not shared yet")
+                .daemon
+                .logs();
+
+            // 状态快照同样单独注入，launch 期间查询状态不会被阻塞
+            let ipfs_status = ipfs_state
+                .try_lock()
+                .expect("IPFS state is not shared yet")
+                .status();
 
             // 注入全局状态
             app.manage(ipfs_state.clone());
+            app.manage(ipfs_status);
+            app.manage(daemon_logs);
+            let metrics: MetricsHandle = Arc::new(MetricsHistory::new(ipfs::metrics::HISTORY_CAPACITY));
+            app.manage(metrics.clone());
             app.manage(planet_store_handle.clone());
+            app.manage(settings_handle);
             let state = ipfs_state.clone();
 
+            // 定期重新发布 IPNS
+            publish::republish::start(app_handle.clone());
+
+            // daemon 崩溃监控
+            ipfs::watchdog::start(ipfs_state.clone(), app_handle.clone());
+
+            // 带宽与 Peer 指标采样
+            ipfs::metrics::start(ipfs_state.clone(), metrics, app_handle.clone());
+
+            // 按策略自动 GC
+            ipfs::gc::start(ipfs_state.clone(), planet_store_handle, app_handle.clone());
+
             // 异步启动 IPFS daemon
             tauri::async_runtime::spawn(async move {
                 ipfs::state::auto_start(state, app_handle.clone()).await;
@@ -103,7 +158,7 @@ fn main() {
                     let state: tauri::State<IpfsStateHandle> = app_handle.state();
                     let state_clone = state.inner().clone();
                     tauri::async_runtime::block_on(async {
-                        ipfs::state::graceful_shutdown(state_clone).await;
+                        ipfs::state::graceful_shutdown(state_clone, app_handle).await;
                     });
                 }
                 tauri::RunEvent::Exit => {
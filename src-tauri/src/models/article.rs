@@ -350,3 +350,29 @@ impl From<&MyArticle> for PublicArticle {
         }
     }
 }
+
+impl MyArticle {
+    /// 从已发布站点中的 article.json 还原文章（导入 Planet 归档时使用）
+    /// 站点中没有的字段取默认值
+    pub fn from_public(planet_id: Uuid, article: &PublicArticle) -> Self {
+        Self {
+            id: article.id,
+            planet_id,
+            title: article.title.clone(),
+            content: article.content.clone(),
+            created: article.created,
+            updated: article.created,
+            link: article.link.clone(),
+            slug: (!article.slug.is_empty()).then(|| article.slug.clone()),
+            hero_image: None,
+            external_link: None,
+            attachments: article.attachments.clone(),
+            tags: article.tags.clone(),
+            pinned: article.pinned,
+            article_type: Some(ArticleType::Blog),
+            summary: None,
+            is_included_in_navigation: Some(false),
+            navigation_weight: Some(1),
+        }
+    }
+}
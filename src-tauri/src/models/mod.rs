@@ -1,3 +1,4 @@
 pub mod planet;
 pub mod article;
-pub mod draft;
\ No newline at end of file
+pub mod draft;
+pub mod settings;
\ No newline at end of file
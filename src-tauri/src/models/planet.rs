@@ -5,10 +5,16 @@ use std::fs;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use anyhow::{anyhow, Result};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use tauri::AppHandle;
 
 use crate::helpers::paths;
+use crate::ipfs::backend::IpfsBackend;
+use crate::ipfs::pinning::PinningService;
+use crate::models::article::PublicArticle;
+
+/// 早期版本创建 Planet 时写入的占位 IPNS 名称，加载时视为尚未生成 key
+const PLACEHOLDER_IPNS: &str = "k51qzi5uqu5dibstm2yxidly22jx94embd7j3xjstfk65ulictn2ajnjvpiac7";
 
 // ============================================================
 // PlanetType 枚举
@@ -72,6 +78,22 @@ pub struct MyPlanet {
     pub filebase_pin_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filebase_api_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filebase_request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filebase_pin_cid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filebase_pin_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filebase_pin_status_retrieved: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinning_services: Vec<PinningService>,
+    /// 最近一次定期重新发布 IPNS 的时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_republished: Option<DateTime<Utc>>,
+    /// 最近一次重新发布失败的原因，成功后清除
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub republish_error: Option<String>,
 
     // 其他配置（Phase 3+ 实现）
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -122,18 +144,41 @@ impl MyPlanet {
         self.base_path(app).join("favicon.ico")
     }
 
+    /// 渲染后的公开站点目录（发布时整体 add 到 IPFS）
+    pub fn public_path(&self, app: &AppHandle) -> PathBuf {
+        paths::get_data_path(app).join("Public").join(self.id.to_string())
+    }
+
     // ============================================================
     // CRUD 操作
     // ============================================================
 
     /// 创建新的 Planet
-    pub fn create(name: String, about: String, template_name: String, app: &AppHandle) -> Result<Self> {
+    /// backend 不可用时 ipns 留空，下次 daemon 启动后由 ensure_planet_keys() 补齐
+    pub async fn create<B: IpfsBackend>(
+        name: String,
+        about: String,
+        template_name: String,
+        backend: Option<&B>,
+        app: &AppHandle,
+    ) -> Result<Self> {
         let id = Uuid::new_v4();
         let now = Utc::now();
-        
-        // 生成 IPNS key（Phase 1 中已实现）
-        // 这里先使用占位符，Phase 3 发布时会生成真正的 IPNS key
-        let ipns = format!("k51qzi5uqu5dibstm2yxidly22jx94embd7j3xjstfk65ulictn2ajnjvpiac7");
+
+        // 以 Planet UUID 作为 key 名生成 IPNS key
+        let ipns = match backend {
+            Some(backend) => match backend.generate_key(&id.to_string()).await {
+                Ok(key_id) => key_id,
+                Err(e) => {
+                    warn!("Failed to generate IPNS key for planet {}: {}", id, e);
+                    String::new()
+                }
+            },
+            None => {
+                info!("IPFS daemon unavailable, IPNS key for planet {} is deferred", id);
+                String::new()
+            }
+        };
 
         let planet = Self {
             id,
@@ -157,6 +202,13 @@ impl MyPlanet {
             filebase_enabled: None,
             filebase_pin_name: None,
             filebase_api_token: None,
+            filebase_request_id: None,
+            filebase_pin_cid: None,
+            filebase_pin_status: None,
+            filebase_pin_status_retrieved: None,
+            pinning_services: Vec::new(),
+            last_republished: None,
+            republish_error: None,
             do_not_index: Some(false),
             prewarm_new_post: Some(true),
         };
@@ -243,6 +295,35 @@ impl MyPlanet {
         Ok(())
     }
 
+    // ============================================================
+    // IPNS Key
+    // ============================================================
+
+    /// IPFS keystore 中该 Planet 的 key 名（即 Planet UUID）
+    pub fn key_name(&self) -> String {
+        self.id.to_string()
+    }
+
+    /// 是否已经拥有真实的 IPNS key
+    pub fn has_ipns_key(&self) -> bool {
+        !self.ipns.is_empty() && self.ipns != PLACEHOLDER_IPNS
+    }
+
+    /// 取得 Planet 的 IPNS key id：keystore 中已有同名 key 时直接复用，否则生成
+    /// 不修改也不保存 Planet，由调用方在 store 锁内写回 ipns
+    pub async fn ipns_key_id<B: IpfsBackend>(&self, backend: &B) -> Result<String> {
+        let key_name = self.key_name();
+        let existing = backend
+            .list_keys()
+            .await?
+            .into_iter()
+            .find(|k| k.name == key_name);
+        match existing {
+            Some(key) => Ok(key.id),
+            None => backend.generate_key(&key_name).await,
+        }
+    }
+
     /// 删除 Planet（包括所有文章和草稿）
     pub fn delete(&self, app: &AppHandle) -> Result<()> {
         let base_path = self.base_path(app);
@@ -250,6 +331,10 @@ impl MyPlanet {
             fs::remove_dir_all(&base_path)?;
             info!("Deleted planet: {} ({})", self.name, self.id);
         }
+        let public_path = self.public_path(app);
+        if public_path.exists() {
+            fs::remove_dir_all(&public_path)?;
+        }
         Ok(())
     }
 }
@@ -452,6 +537,7 @@ pub struct PublicPlanet {
     pub id: Uuid,
     pub name: String,
     pub about: String,
+    pub ipns: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author_name: Option<String>,
     pub created: DateTime<Utc>,
@@ -460,6 +546,8 @@ pub struct PublicPlanet {
     pub twitter_username: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub github_username: Option<String>,
+    #[serde(default)]
+    pub articles: Vec<PublicArticle>,
     // ... 其他公开字段
 }
 
@@ -469,11 +557,13 @@ impl From<&MyPlanet> for PublicPlanet {
             id: planet.id,
             name: planet.name.clone(),
             about: planet.about.clone(),
+            ipns: planet.ipns.clone(),
             author_name: planet.author_name.clone(),
             created: planet.created,
             updated: planet.updated,
             twitter_username: planet.twitter_username.clone(),
             github_username: planet.github_username.clone(),
+            articles: Vec::new(),
         }
     }
 }
\ No newline at end of file
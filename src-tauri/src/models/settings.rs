@@ -0,0 +1,418 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use tracing::{debug, error};
+use tauri::{AppHandle, Manager};
+
+use crate::helpers::{net, paths};
+use crate::ipfs::binary;
+use crate::ipfs::models::IpnsPublishOptions;
+use crate::ipfs::profile::{self, NetworkProfile};
+
+// ============================================================
+// AppSettings 应用级设置
+// 保存在 {data}/Planet/Settings.json，缺失的字段使用默认值
+// ============================================================
+
+/// 默认的预热网关（路径式网关根地址）
+pub const DEFAULT_PREWARM_GATEWAYS: &[&str] = &[
+    "https://ipfs.io",
+    "https://dweb.link",
+    "https://w3s.link",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    /// 发布新文章后用于预热的网关
+    #[serde(default = "default_prewarm_gateways")]
+    pub prewarm_gateways: Vec<String>,
+    /// 单个预热请求的超时（秒）
+    #[serde(default = "default_prewarm_timeout_secs")]
+    pub prewarm_timeout_secs: u64,
+    /// IPNS 定期重新发布的间隔（秒）
+    #[serde(default = "default_republish_interval_secs")]
+    pub republish_interval_secs: u64,
+    /// IPNS 记录有效期（秒）
+    #[serde(default = "default_ipns_lifetime_secs")]
+    pub ipns_lifetime_secs: u64,
+    /// IPNS 记录 TTL（秒）
+    #[serde(default = "default_ipns_ttl_secs")]
+    pub ipns_ttl_secs: u64,
+    /// Kubo swarm 端口
+    #[serde(default = "default_swarm_port")]
+    pub swarm_port: PortPreference,
+    /// Kubo API 端口
+    #[serde(default = "default_api_port")]
+    pub api_port: PortPreference,
+    /// Kubo Gateway 端口
+    #[serde(default = "default_gateway_port")]
+    pub gateway_port: PortPreference,
+    /// 仓库容量上限（GB），写入 Datastore.StorageMax；None 时使用 Kubo 默认值（10GB）
+    #[serde(default)]
+    pub storage_max_gb: Option<u64>,
+    /// 自动 GC 策略
+    #[serde(default)]
+    pub gc_policy: GcPolicy,
+    /// 使用的 Kubo 节点
+    #[serde(default)]
+    pub kubo_node: KuboNode,
+    /// 自定义的 Kubo 可执行文件路径；环境变量 PLANET_KUBO_PATH 优先
+    /// 自定义的程序不在打包清单中，按 kubo_binary_sha256 校验
+    #[serde(default)]
+    pub kubo_binary_path: Option<String>,
+    /// 自定义 Kubo 的 SHA-256（十六进制），设置 kubo_binary_path 时必填
+    #[serde(default)]
+    pub kubo_binary_sha256: Option<String>,
+    /// 当前使用的网络配置方案名称
+    #[serde(default = "default_network_profile")]
+    pub network_profile: String,
+    /// 可编辑的网络配置方案，默认为内置的 low-power / default / server
+    #[serde(default = "profile::builtin_profiles")]
+    pub network_profiles: Vec<NetworkProfile>,
+}
+
+/// 使用的 Kubo 节点
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KuboNode {
+    /// 随应用打包的 Kubo，由应用负责 setup / launch / shutdown
+    #[default]
+    Bundled,
+    /// 已有的本机或远程 Kubo 节点，应用只通过其 API 访问，不启动也不关闭它
+    External {
+        /// API multiaddr，如 /ip4/192.168.1.10/tcp/5001 或 /dns4/ipfs.example.com/tcp/443/https
+        api: String,
+        /// 网关地址，如 http://192.168.1.10:8080
+        #[serde(default)]
+        gateway: Option<String>,
+        #[serde(default)]
+        auth: Option<KuboAuth>,
+    },
+}
+
+impl KuboNode {
+    /// 是否由应用管理（可以 setup / launch / shutdown）
+    pub fn is_managed(&self) -> bool {
+        matches!(self, Self::Bundled)
+    }
+}
+
+/// 访问外部节点 API 的认证方式，对应 Kubo 的 API.Authorizations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KuboAuth {
+    Basic { username: String, password: String },
+    Bearer { token: String },
+}
+
+impl KuboAuth {
+    pub fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::Basic { username, password } => request.basic_auth(username, Some(password)),
+            Self::Bearer { token } => request.bearer_auth(token),
+        }
+    }
+}
+
+/// 自动 GC 策略，任一条件满足即触发；全部为空时不自动 GC
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GcPolicy {
+    /// 仓库用量达到 StorageMax 的百分比
+    #[serde(default)]
+    pub watermark_percent: Option<u8>,
+    /// 定期 GC 的间隔（秒）
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+    /// 每发布 N 次后 GC
+    #[serde(default)]
+    pub after_publishes: Option<u32>,
+}
+
+impl GcPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.watermark_percent.is_some()
+            || self.interval_secs.is_some()
+            || self.after_publishes.is_some()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(percent) = self.watermark_percent {
+            if percent == 0 || percent > 100 {
+                return Err(anyhow!("GC watermark must be between 1 and 100 percent"));
+            }
+        }
+        if self.interval_secs == Some(0) {
+            return Err(anyhow!("GC interval must be greater than 0"));
+        }
+        if self.after_publishes == Some(0) {
+            return Err(anyhow!("GC publish count must be greater than 0"));
+        }
+        Ok(())
+    }
+}
+
+/// 单个 daemon 端口的选择方式
+/// 优先级：固定端口 > 上次使用且仍空闲的端口 > 在范围内扫描
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortPreference {
+    /// 用户指定的固定端口；被占用时 setup 失败，不会改用其他端口
+    #[serde(default)]
+    pub fixed: Option<u16>,
+    /// 扫描范围（含两端）
+    pub range_start: u16,
+    pub range_end: u16,
+    /// 上次 setup 实际使用的端口
+    #[serde(default)]
+    pub last_used: Option<u16>,
+}
+
+impl PortPreference {
+    pub fn new(range_start: u16, range_end: u16) -> Self {
+        Self {
+            fixed: None,
+            range_start,
+            range_end,
+            last_used: None,
+        }
+    }
+
+    /// 按优先级选出一个可用端口
+    pub fn choose(&self, label: &str) -> Result<u16> {
+        self.choose_with(net::is_port_available).ok_or_else(|| match self.fixed {
+            Some(port) => anyhow!("{} port {} is already in use", label, port),
+            None => anyhow!(
+                "Unable to find open {} port in {}-{}",
+                label,
+                self.range_start,
+                self.range_end
+            ),
+        })
+    }
+
+    pub fn choose_with<F>(&self, is_available: F) -> Option<u16>
+    where
+        F: Fn(u16) -> bool,
+    {
+        if let Some(port) = self.fixed {
+            return is_available(port).then_some(port);
+        }
+        let range = self.range_start..=self.range_end;
+        // 调整过范围时不再复用范围外的旧端口
+        if let Some(port) = self
+            .last_used
+            .filter(|p| range.contains(p) && is_available(*p))
+        {
+            return Some(port);
+        }
+        range.into_iter().find(|p| is_available(*p))
+    }
+}
+
+fn default_prewarm_gateways() -> Vec<String> {
+    DEFAULT_PREWARM_GATEWAYS.iter().map(|g| g.to_string()).collect()
+}
+
+fn default_prewarm_timeout_secs() -> u64 {
+    60
+}
+
+fn default_republish_interval_secs() -> u64 {
+    4 * 60 * 60
+}
+
+fn default_ipns_lifetime_secs() -> u64 {
+    7 * 24 * 60 * 60
+}
+
+fn default_ipns_ttl_secs() -> u64 {
+    60 * 60
+}
+
+fn default_network_profile() -> String {
+    profile::DEFAULT.to_string()
+}
+
+fn default_swarm_port() -> PortPreference {
+    PortPreference::new(4001, 4011)
+}
+
+fn default_api_port() -> PortPreference {
+    PortPreference::new(5981, 5991)
+}
+
+fn default_gateway_port() -> PortPreference {
+    PortPreference::new(18181, 18191)
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            prewarm_gateways: default_prewarm_gateways(),
+            prewarm_timeout_secs: default_prewarm_timeout_secs(),
+            republish_interval_secs: default_republish_interval_secs(),
+            ipns_lifetime_secs: default_ipns_lifetime_secs(),
+            ipns_ttl_secs: default_ipns_ttl_secs(),
+            swarm_port: default_swarm_port(),
+            api_port: default_api_port(),
+            gateway_port: default_gateway_port(),
+            storage_max_gb: None,
+            gc_policy: GcPolicy::default(),
+            kubo_node: KuboNode::default(),
+            kubo_binary_path: None,
+            kubo_binary_sha256: None,
+            network_profile: default_network_profile(),
+            network_profiles: profile::builtin_profiles(),
+        }
+    }
+}
+
+/// 全局设置的类型别名
+pub type SettingsHandle = Arc<Mutex<AppSettings>>;
+
+impl AppSettings {
+    pub fn path(app: &AppHandle) -> PathBuf {
+        paths::get_data_path(app).join("Settings.json")
+    }
+
+    /// 当前生效的设置；未注入全局设置时返回默认值
+    pub fn current(app: &AppHandle) -> Self {
+        app.try_state::<SettingsHandle>()
+            .and_then(|s| s.lock().ok().map(|s| s.clone()))
+            .unwrap_or_default()
+    }
+
+    /// 修改全局设置并保存到磁盘
+    pub fn update<F>(app: &AppHandle, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut AppSettings),
+    {
+        let handle = app
+            .try_state::<SettingsHandle>()
+            .ok_or_else(|| anyhow!("App settings not initialized"))?;
+        let mut settings = handle.lock().map_err(|e| anyhow!("{}", e))?;
+        f(&mut settings);
+        settings.save(app)
+    }
+
+    pub fn ipns_options(&self) -> IpnsPublishOptions {
+        IpnsPublishOptions {
+            lifetime: Duration::from_secs(self.ipns_lifetime_secs),
+            ttl: Duration::from_secs(self.ipns_ttl_secs),
+        }
+    }
+
+    /// 当前选中的网络配置方案
+    pub fn active_network_profile(&self) -> Result<&NetworkProfile> {
+        self.network_profiles
+            .iter()
+            .find(|p| p.name == self.network_profile)
+            .ok_or_else(|| anyhow!("Network profile {} does not exist", self.network_profile))
+    }
+
+    /// 校验所有网络配置方案：名称唯一、内容合法，且选中的方案存在
+    pub fn validate_network_profiles(&self) -> Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for profile in &self.network_profiles {
+            profile.validate()?;
+            if !names.insert(profile.name.as_str()) {
+                return Err(anyhow!("Duplicate network profile name {}", profile.name));
+            }
+        }
+        self.active_network_profile().map(|_| ())
+    }
+
+    /// 校验前端提交的设置
+    pub fn validate(&self) -> Result<()> {
+        self.validate_network_profiles()?;
+        if self.republish_interval_secs == 0 {
+            return Err(anyhow!("Republish interval must be greater than 0"));
+        }
+        if self.storage_max_gb == Some(0) {
+            return Err(anyhow!("Storage max must be greater than 0 GB"));
+        }
+        if self.kubo_binary_path.as_deref().is_some_and(|p| !p.trim().is_empty()) {
+            let hash = self.kubo_binary_sha256.as_deref().map(str::trim).unwrap_or_default();
+            if !binary::is_sha256_hex(hash) {
+                return Err(anyhow!(
+                    "A custom Kubo binary requires its SHA-256 (64 hex characters)"
+                ));
+            }
+        }
+        self.gc_policy.validate()
+    }
+
+    pub fn republish_interval(&self) -> Duration {
+        Duration::from_secs(self.republish_interval_secs)
+    }
+
+    /// 从磁盘加载设置；文件不存在或解析失败时返回默认值
+    pub fn load(app: &AppHandle) -> Self {
+        let path = Self::path(app);
+        if !path.exists() {
+            return Self::default();
+        }
+        match fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|c| serde_json::from_str(&c).map_err(anyhow::Error::from))
+        {
+            Ok(settings) => settings,
+            Err(e) => {
+                error!("Failed to load settings, using defaults: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// 保存设置到磁盘
+    pub fn save(&self, app: &AppHandle) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::path(app), content)?;
+        debug!("Saved app settings");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_port_preference_order() {
+        let mut pref = PortPreference::new(4001, 4011);
+        pref.last_used = Some(4005);
+        assert_eq!(pref.choose_with(|_| true), Some(4005));
+        // 上次的端口被占用时回退到扫描
+        assert_eq!(pref.choose_with(|p| p != 4005), Some(4001));
+
+        pref.fixed = Some(4100);
+        assert_eq!(pref.choose_with(|_| true), Some(4100));
+        assert_eq!(pref.choose_with(|p| p != 4100), None);
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_limits() {
+        let settings = AppSettings::default();
+        assert!(settings.validate().is_ok());
+
+        let mut invalid = settings.clone();
+        invalid.gc_policy.watermark_percent = Some(0);
+        assert!(invalid.validate().is_err());
+        invalid.gc_policy.watermark_percent = Some(101);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = settings.clone();
+        invalid.gc_policy.interval_secs = Some(0);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = settings.clone();
+        invalid.storage_max_gb = Some(0);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = settings;
+        invalid.republish_interval_secs = 0;
+        assert!(invalid.validate().is_err());
+    }
+}
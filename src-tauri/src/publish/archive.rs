@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::ipfs::backend::IpfsBackend;
+use crate::ipfs::car;
+use crate::ipfs::daemon::IpfsClient;
+use crate::models::article::MyArticle;
+use crate::models::planet::{MyPlanet, PublicPlanet};
+
+// ============================================================
+// Planet 归档：用于离线传输和存档
+// 导出目录 {planet_id}.planet/ 包含：
+// - planet.car：last_published_cid 对应的站点 DAG（dag/export）
+// - planet.json：Planet 元数据（不含 Filebase / pinning 服务凭据）
+// 导入时先校验 CAR 根与 planet.json 中的 last_published_cid 一致
+// ============================================================
+
+pub const CAR_FILE: &str = "planet.car";
+pub const METADATA_FILE: &str = "planet.json";
+
+/// 导出的元数据：去掉凭据，其余与本地 planet.json 相同
+pub fn export_metadata(planet: &MyPlanet) -> MyPlanet {
+    let mut planet = planet.clone();
+    planet.filebase_api_token = None;
+    planet.pinning_services.clear();
+    planet
+}
+
+/// 将 Planet 最近一次发布的内容导出到 target_dir 下，返回归档目录
+pub async fn export(planet: &MyPlanet, client: &IpfsClient, target_dir: &Path) -> Result<PathBuf> {
+    let cid = planet
+        .last_published_cid
+        .as_deref()
+        .ok_or_else(|| anyhow!("Planet {} has not been published yet", planet.id))?;
+    let bundle = target_dir.join(format!("{}.planet", planet.id));
+    if bundle.exists() {
+        return Err(anyhow!("{} already exists", bundle.display()));
+    }
+    fs::create_dir_all(&bundle)?;
+
+    let result = async {
+        let car_path = bundle.join(CAR_FILE);
+        client.dag_export(cid, &car_path).await?;
+        verify_roots(&car_path, cid)?;
+        let metadata = serde_json::to_string_pretty(&export_metadata(planet))?;
+        fs::write(bundle.join(METADATA_FILE), metadata)?;
+        Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&bundle);
+        return Err(e);
+    }
+
+    info!("Exported planet {} ({}) to {:?}", planet.id, cid, bundle);
+    Ok(bundle)
+}
+
+/// 读取归档中的 Planet 元数据，并校验 CAR 根与 last_published_cid 一致
+pub fn read_bundle(bundle: &Path) -> Result<MyPlanet> {
+    let content = fs::read_to_string(bundle.join(METADATA_FILE))
+        .map_err(|e| anyhow!("Invalid planet archive, {} is missing: {}", METADATA_FILE, e))?;
+    let planet: MyPlanet = serde_json::from_str(&content)?;
+    let cid = planet
+        .last_published_cid
+        .as_deref()
+        .ok_or_else(|| anyhow!("Planet archive has no published CID"))?;
+    verify_roots(&bundle.join(CAR_FILE), cid)?;
+    Ok(planet)
+}
+
+fn verify_roots(car_path: &Path, expected: &str) -> Result<()> {
+    let roots = car::read_roots(car_path)?;
+    if roots != [expected] {
+        return Err(anyhow!(
+            "CAR roots {:?} do not match the planet's published CID {}",
+            roots,
+            expected
+        ));
+    }
+    Ok(())
+}
+
+/// 导入归档：导入并 pin CAR 内容，从站点中还原文章和附件，保存 Planet
+/// 本机 keystore 中没有原 IPNS key 时清空 ipns，daemon 会为其生成新 key（Planet 地址随之改变）
+/// 还原失败时移除本次导入新增的 pin
+/// 调用方需确认 daemon 在线，并将返回的 Planet 加入 store
+pub async fn import(bundle: &Path, client: &IpfsClient, app: &AppHandle) -> Result<MyPlanet> {
+    let mut planet = read_bundle(bundle)?;
+    if planet.base_path(app).exists() {
+        return Err(anyhow!("Planet {} already exists", planet.id));
+    }
+    let cid = planet.last_published_cid.clone().unwrap_or_default();
+    // 导入前已 pin 的内容（如关注的同一站点）失败时不能取消 pin
+    let was_pinned = client.pin_type(&cid).await?.is_some();
+
+    let roots = client.dag_import(&bundle.join(CAR_FILE)).await?;
+    let result = async {
+        if !roots.contains(&cid) {
+            return Err(anyhow!("Imported CAR roots {:?} do not include {}", roots, cid));
+        }
+
+        let key_name = planet.key_name();
+        if !client.list_keys().await?.iter().any(|k| k.name == key_name) {
+            warn!(
+                "IPNS key of planet {} is not in the keystore, a new key will be generated",
+                planet.id
+            );
+            planet.ipns = String::new();
+            // 新 key 尚未发布过，下次发布不能因 CID 未变化而跳过
+            planet.last_published = None;
+            planet.last_published_cid = None;
+        }
+
+        restore(&planet, &cid, client, app).await.inspect_err(|_| {
+            let _ = fs::remove_dir_all(planet.base_path(app));
+        })
+    }
+    .await;
+
+    let article_count = match result {
+        Ok(count) => count,
+        Err(e) => {
+            for root in roots.iter().filter(|root| !was_pinned || **root != cid) {
+                if let Err(e) = client.unpin(root).await {
+                    warn!("Failed to unpin imported root {}: {}", root, e);
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    info!(
+        "Imported planet {} ({}) with {} article(s)",
+        planet.id,
+        cid,
+        article_count
+    );
+    Ok(planet)
+}
+
+/// 从已发布站点中还原文章和附件，并保存 Planet，返回文章数
+async fn restore(planet: &MyPlanet, cid: &str, client: &IpfsClient, app: &AppHandle) -> Result<usize> {
+    let site: PublicPlanet =
+        serde_json::from_slice(&client.cat(&format!("/ipfs/{}/planet.json", cid)).await?)?;
+    fs::create_dir_all(planet.articles_path(app))?;
+    fs::create_dir_all(planet.drafts_path(app))?;
+    for public in &site.articles {
+        let article = MyArticle::from_public(planet.id, public);
+        let attachments_path = article.attachments_path(planet, app);
+        for attachment in &article.attachments {
+            // 附件名来自归档内容，只接受单层文件名
+            if Path::new(&attachment.name).file_name() != Some(attachment.name.as_ref()) {
+                warn!("Skipping attachment with invalid name: {:?}", attachment.name);
+                continue;
+            }
+            let path = format!("/ipfs/{}/{}/{}", cid, article.id, attachment.name);
+            let content = client.cat(&path).await?;
+            fs::create_dir_all(&attachments_path)?;
+            fs::write(attachments_path.join(&attachment.name), content)?;
+        }
+        article.save(planet, app)?;
+    }
+    planet.save(app)?;
+    Ok(site.articles.len())
+}
@@ -0,0 +1,36 @@
+use tracing::warn;
+
+use crate::ipfs::pinning::PinningClient;
+use crate::models::planet::MyPlanet;
+
+// ============================================================
+// Filebase 远程 Pinning
+// 对应原项目 MyPlanetModel+Filebase.swift
+// Filebase 实现了 IPFS Pinning Service API，按 name 区分同一 Planet 的 pin
+// ============================================================
+
+/// Filebase Pinning API 根地址
+pub const FILEBASE_ENDPOINT: &str = "https://api.filebase.io/v1/ipfs";
+
+/// Filebase 客户端
+pub fn client(token: &str) -> PinningClient {
+    PinningClient::new(FILEBASE_ENDPOINT, token)
+}
+
+/// 发布成功后用于替换该 Planet 旧 pin 的客户端和 pin 名称
+/// 未启用 Filebase 或未配置 token 时返回 None
+pub fn published_pin(planet: &MyPlanet) -> Option<(PinningClient, String)> {
+    if !planet.filebase_enabled.unwrap_or(false) {
+        return None;
+    }
+    let Some(token) = planet.filebase_api_token.as_deref().filter(|t| !t.is_empty()) else {
+        warn!("Filebase is enabled for planet {} but no API token is set", planet.id);
+        return None;
+    };
+    let name = planet
+        .filebase_pin_name
+        .clone()
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| planet.id.to_string());
+    Some((client(token), name))
+}
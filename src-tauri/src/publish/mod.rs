@@ -0,0 +1,189 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::ipfs::backend::IpfsBackend;
+use crate::ipfs::models::{IpfsPublished, IpnsPublishOptions};
+use crate::models::article::MyArticle;
+use crate::models::planet::MyPlanet;
+use crate::models::settings::AppSettings;
+use crate::template;
+
+pub mod archive;
+pub mod filebase;
+pub mod prewarm;
+pub mod remote_pin;
+pub mod republish;
+pub mod state;
+
+use state::PublishStage;
+
+// ============================================================
+// 发布流程：渲染 → ipfs add → name/publish
+// 对应原项目 MyPlanetModel.publish()
+// ============================================================
+
+/// 发布结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishResult {
+    /// 渲染后站点的根 CID
+    pub cid: String,
+    /// 是否实际执行了 IPNS 发布；CID 与上次发布相同时为 false
+    pub published: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipns: Option<IpfsPublished>,
+}
+
+/// 发布 Planet 到 IPFS/IPNS
+/// 先离线计算站点 CID，与 last_published_cid 相同时跳过 add 和 IPNS 发布
+/// 每进入一个阶段调用一次 on_stage；调用方负责在 published 为 true 时记录 last_published / last_published_cid
+pub async fn publish_planet<B, F>(
+    planet: &MyPlanet,
+    backend: &B,
+    app: &AppHandle,
+    on_stage: F,
+) -> Result<PublishResult>
+where
+    B: IpfsBackend,
+    F: Fn(PublishStage) + Sync,
+{
+    if !planet.has_ipns_key() {
+        return Err(anyhow!(
+            "Planet {} has no IPNS key yet, launch IPFS first",
+            planet.id
+        ));
+    }
+
+    // 1. 渲染公开站点
+    on_stage(PublishStage::Rendering);
+    let public_path = render(planet, app)?;
+
+    let options = AppSettings::current(app).ipns_options();
+    publish_site(planet, backend, &public_path, options, on_stage).await
+}
+
+/// 将已渲染的站点目录添加到 IPFS 并发布到 IPNS（不依赖 AppHandle）
+pub async fn publish_site<B, F>(
+    planet: &MyPlanet,
+    backend: &B,
+    public_path: &Path,
+    options: IpnsPublishOptions,
+    on_stage: F,
+) -> Result<PublishResult>
+where
+    B: IpfsBackend,
+    F: Fn(PublishStage) + Sync,
+{
+    // 2. 离线计算 CID，内容未变化时跳过
+    on_stage(PublishStage::Hashing);
+    let cid = backend.hash_directory(public_path).await?;
+    if planet.last_published_cid.as_deref() == Some(cid.as_str()) {
+        info!("Planet {} is unchanged ({}), skipping publish", planet.id, cid);
+        return Ok(PublishResult {
+            cid,
+            published: false,
+            ipns: None,
+        });
+    }
+
+    // 3. 添加到 IPFS
+    on_stage(PublishStage::Adding);
+    let added_cid = backend.add_directory(public_path).await?;
+    if added_cid != cid {
+        warn!("Added CID {} differs from computed CID {}", added_cid, cid);
+    }
+    info!("Planet {} added to IPFS: {}", planet.id, added_cid);
+
+    // 4. 发布到 IPNS
+    on_stage(PublishStage::PublishingIpns);
+    let published = backend
+        .publish_name(&planet.key_name(), &added_cid, options)
+        .await?;
+    info!("Planet {} published: {}", planet.id, published.value);
+    Ok(PublishResult {
+        cid: added_cid,
+        published: true,
+        ipns: Some(published),
+    })
+}
+
+/// 渲染 Planet 的公开站点，返回输出目录
+pub fn render(planet: &MyPlanet, app: &AppHandle) -> Result<std::path::PathBuf> {
+    let articles = MyArticle::load_all(planet, app)?;
+    let public_path = planet.public_path(app);
+    template::render_planet(planet, &articles, &public_path)?;
+    copy_attachments(planet, &articles, &public_path, app)?;
+    Ok(public_path)
+}
+
+/// 将文章附件拷贝到公开站点的文章目录下
+fn copy_attachments(
+    planet: &MyPlanet,
+    articles: &[MyArticle],
+    output: &Path,
+    app: &AppHandle,
+) -> Result<()> {
+    for article in articles {
+        let attachments_path = article.attachments_path(planet, app);
+        if !attachments_path.exists() {
+            continue;
+        }
+        let target = output.join(article.id.to_string());
+        for entry in fs::read_dir(&attachments_path)? {
+            let entry = entry?;
+            if entry.path().is_file() {
+                fs::copy(entry.path(), target.join(entry.file_name()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipfs::memory::MemoryBackend;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_publish_site_skips_unchanged_content() {
+        let dir = std::env::temp_dir().join(format!("planet-publish-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("index.html"), "<h1>Test</h1>").unwrap();
+
+        let backend = MemoryBackend::new();
+        let mut planet: MyPlanet = serde_json::from_value(serde_json::json!({
+            "id": uuid::Uuid::new_v4(),
+            "name": "Test",
+            "about": "",
+            "created": "2024-01-01T00:00:00Z",
+            "ipns": "",
+            "updated": "2024-01-01T00:00:00Z",
+            "template_name": "Plain",
+        }))
+        .unwrap();
+        planet.ipns = backend.generate_key(&planet.key_name()).await.unwrap();
+        let options = IpnsPublishOptions {
+            lifetime: Duration::from_secs(60),
+            ttl: Duration::from_secs(60),
+        };
+
+        let first = publish_site(&planet, &backend, &dir, options, |_| {})
+            .await
+            .unwrap();
+        assert!(first.published);
+        assert!(backend.is_pinned(&first.cid));
+        assert_eq!(backend.published(&planet.ipns), Some(first.cid.clone()));
+
+        planet.last_published_cid = Some(first.cid.clone());
+        let second = publish_site(&planet, &backend, &dir, options, |_| {})
+            .await
+            .unwrap();
+        assert!(!second.published);
+        assert_eq!(second.cid, first.cid);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
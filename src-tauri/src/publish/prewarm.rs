@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::task::JoinSet;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::models::article::MyArticle;
+use crate::models::planet::MyPlanet;
+use crate::models::settings::AppSettings;
+use crate::store::PlanetStoreHandle;
+
+// ============================================================
+// 网关预热
+// 对应原项目 MyPlanetModel.prewarm()
+// 发布新文章后通过公共网关请求一次新页面，让网关提前缓存内容
+// ============================================================
+
+/// 单个网关、单个页面的预热结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayPrewarmResult {
+    pub gateway: String,
+    pub url: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 一次发布的预热报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrewarmReport {
+    pub planet_id: Uuid,
+    pub cid: String,
+    pub finished_at: DateTime<Utc>,
+    pub results: Vec<GatewayPrewarmResult>,
+}
+
+/// 需要预热的页面路径：站点根 + 上次发布之后新建或修改的文章
+pub fn prewarm_paths(planet: &MyPlanet, articles: &[MyArticle]) -> Vec<String> {
+    let mut paths = vec![String::new()];
+    paths.extend(
+        articles
+            .iter()
+            .filter(|a| planet.last_published.is_none_or(|t| a.updated > t))
+            .map(|a| format!("{}/", a.id)),
+    );
+    paths
+}
+
+/// 并发请求所有网关上的所有页面，结果按网关、路径的顺序返回
+pub async fn prewarm(
+    gateways: &[String],
+    cid: &str,
+    paths: &[String],
+    timeout: Duration,
+) -> Vec<GatewayPrewarmResult> {
+    let client = Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("Failed to create HTTP client");
+
+    let mut tasks = JoinSet::new();
+    let mut index = 0;
+    for gateway in gateways {
+        let base = gateway.trim_end_matches('/');
+        for path in paths {
+            let client = client.clone();
+            let gateway = gateway.clone();
+            let url = format!("{}/ipfs/{}/{}", base, cid, path);
+            tasks.spawn(async move { (index, fetch(&client, gateway, url).await) });
+            index += 1;
+        }
+    }
+
+    let mut results = Vec::with_capacity(index);
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+async fn fetch(client: &Client, gateway: String, url: String) -> GatewayPrewarmResult {
+    let started = Instant::now();
+    // 读完响应体，确保网关完整拉取了内容
+    let response = match client.get(&url).send().await {
+        Ok(response) => {
+            let status = response.status();
+            response.bytes().await.map(|_| status)
+        }
+        Err(e) => Err(e),
+    };
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    match response {
+        Ok(status) => {
+            debug!("Prewarmed {} ({}) in {}ms", url, status, elapsed_ms);
+            GatewayPrewarmResult {
+                gateway,
+                url,
+                ok: status.is_success(),
+                status: Some(status.as_u16()),
+                elapsed_ms,
+                error: None,
+            }
+        }
+        Err(e) => GatewayPrewarmResult {
+            gateway,
+            url,
+            ok: false,
+            status: None,
+            elapsed_ms,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// 发布成功后在后台预热网关
+/// planet 为发布前的快照，用其 last_published 判断哪些文章是新的
+pub fn spawn_prewarm(app: AppHandle, store: PlanetStoreHandle, planet: MyPlanet, cid: String) {
+    if planet.prewarm_new_post != Some(true) {
+        return;
+    }
+    let settings = AppSettings::current(&app);
+    let gateways = settings.prewarm_gateways;
+    let timeout = Duration::from_secs(settings.prewarm_timeout_secs);
+    if gateways.is_empty() {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let articles = match MyArticle::load_all(&planet, &app) {
+            Ok(articles) => articles,
+            Err(e) => {
+                error!("Failed to load articles for prewarm: {}", e);
+                return;
+            }
+        };
+        let paths = prewarm_paths(&planet, &articles);
+        let results = prewarm(&gateways, &cid, &paths, timeout).await;
+        let ok = results.iter().filter(|r| r.ok).count();
+        info!(
+            "Prewarmed planet {} on {} gateway(s): {}/{} succeeded",
+            planet.id,
+            gateways.len(),
+            ok,
+            results.len()
+        );
+
+        let report = PrewarmReport {
+            planet_id: planet.id,
+            cid,
+            finished_at: Utc::now(),
+            results,
+        };
+        if let Ok(mut store) = store.lock() {
+            store.record_prewarm(report, &app);
+        };
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_server::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn test_prewarm_fetches_all_paths_on_each_gateway() {
+        let server = MockServer::start(|_| MockResponse::text(200, "<html></html>")).await;
+        let gateways = vec![server.url.clone()];
+        let paths = vec![String::new(), "article-1/".to_string()];
+
+        let results = prewarm(&gateways, "bafy-site", &paths, Duration::from_secs(5)).await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.ok && r.status == Some(200)));
+        let mut requested: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+        requested.sort();
+        assert_eq!(
+            requested,
+            vec!["/ipfs/bafy-site/", "/ipfs/bafy-site/article-1/"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prewarm_reports_each_gateway() {
+        let server = MockServer::start(|_| MockResponse::text(504, "timeout")).await;
+        // 绑定后立即释放的端口，连接会被拒绝
+        let closed = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        let gateways = vec![server.url.clone(), closed.clone()];
+
+        let results = prewarm(&gateways, "bafy-site", &[String::new()], Duration::from_secs(5)).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].gateway, server.url);
+        assert!(!results[0].ok);
+        assert_eq!(results[0].status, Some(504));
+        assert_eq!(results[1].gateway, closed);
+        assert!(!results[1].ok);
+        assert!(results[1].error.is_some());
+    }
+}
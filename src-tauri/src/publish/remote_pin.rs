@@ -0,0 +1,84 @@
+use tauri::AppHandle;
+use tracing::{error, info};
+
+use crate::ipfs::pinning::{PinStatus, PIN_POLL_INTERVAL, PIN_POLL_TIMEOUT};
+use crate::models::planet::MyPlanet;
+use crate::publish::filebase;
+use crate::store::PlanetStoreHandle;
+
+// ============================================================
+// 发布后的远程 Pinning
+// Filebase 与 Planet 配置的每个 Pinning 服务各自在后台提交并轮询，互不阻塞
+// ============================================================
+
+/// pin 状态的记录位置
+#[derive(Debug, Clone)]
+enum PinTarget {
+    Filebase,
+    Service(String),
+}
+
+/// 后台将新发布的 CID 提交到所有远程 Pinning 服务，不阻塞发布结果返回
+/// 新 pin 完成后才删除旧 pin
+pub fn spawn_remote_pins(app: AppHandle, store: PlanetStoreHandle, planet: MyPlanet, cid: String) {
+    let mut targets = Vec::new();
+    if let Some((client, name)) = filebase::published_pin(&planet) {
+        targets.push((PinTarget::Filebase, client, name));
+    }
+    for service in &planet.pinning_services {
+        targets.push((
+            PinTarget::Service(service.name.clone()),
+            service.client(),
+            planet.id.to_string(),
+        ));
+    }
+
+    for (target, client, pin_name) in targets {
+        let app = app.clone();
+        let store = store.clone();
+        let planet_id = planet.id;
+        let cid = cid.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = client
+                .replace_pin(
+                    &cid,
+                    &pin_name,
+                    PIN_POLL_INTERVAL,
+                    PIN_POLL_TIMEOUT,
+                    |status| record(&app, &store, planet_id, &target, status, true),
+                    |status| record(&app, &store, planet_id, &target, status, false),
+                )
+                .await;
+            match result {
+                Ok(status) => {
+                    info!("Remote pin {:?} for planet {} is {}", target, planet_id, status.status)
+                }
+                Err(e) => error!("Remote pin {:?} failed for planet {}: {}", target, planet_id, e),
+            }
+        });
+    }
+}
+
+/// 记录 pin 状态；submitted 为 true 时为新提交的请求，否则是轮询到的状态变化
+fn record(
+    app: &AppHandle,
+    store: &PlanetStoreHandle,
+    planet_id: uuid::Uuid,
+    target: &PinTarget,
+    status: &PinStatus,
+    submitted: bool,
+) {
+    let Ok(mut store) = store.lock() else {
+        return;
+    };
+    let result = match target {
+        PinTarget::Filebase => store.record_filebase_pin(planet_id, status, submitted, app),
+        PinTarget::Service(name) => {
+            store.record_remote_pin(planet_id, name, status, submitted, app)
+        }
+    };
+    if let Err(e) = result {
+        error!("Failed to record remote pin status: {}", e);
+    }
+    store.emit_state_changed(app);
+}
@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::ipfs::backend::IpfsBackend;
+use crate::ipfs::state::{online_client, IpfsStateHandle};
+use crate::models::planet::MyPlanet;
+use crate::models::settings::AppSettings;
+use crate::store::PlanetStoreHandle;
+
+// ============================================================
+// IPNS 定期重新发布
+// IPNS 记录会过期，后台定期把 last_published_cid 重新发布到各 Planet 的 key
+// 启动时记录已超过 TTL 的 Planet 立即重新发布
+// ============================================================
+
+/// 调度循环的检查间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 失败重试的初始等待时间，之后每次翻倍
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(60);
+
+/// 在 main.rs setup 中调用，启动后台调度任务
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut scheduler = Scheduler::default();
+        loop {
+            scheduler.tick(&app).await;
+            tokio::time::sleep(TICK_INTERVAL).await;
+        }
+    });
+}
+
+/// 单个 Planet 的失败重试状态
+#[derive(Debug, Clone, Copy)]
+struct Retry {
+    attempts: u32,
+    next_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct Scheduler {
+    /// daemon 上线后是否已完成首轮（按 TTL 判断）检查
+    started: bool,
+    retries: HashMap<Uuid, Retry>,
+}
+
+impl Scheduler {
+    async fn tick(&mut self, app: &AppHandle) {
+        let (Some(ipfs), Some(store)) = (
+            app.try_state::<IpfsStateHandle>().map(|s| s.inner().clone()),
+            app.try_state::<PlanetStoreHandle>().map(|s| s.inner().clone()),
+        ) else {
+            return;
+        };
+        if !ipfs.lock().await.is_online() {
+            return;
+        }
+
+        let settings = AppSettings::current(app);
+        let interval = settings.republish_interval();
+        // 首轮使用 TTL 判断，之后按配置的间隔
+        let threshold = if self.started {
+            interval
+        } else {
+            Duration::from_secs(settings.ipns_ttl_secs)
+        };
+        self.started = true;
+
+        let now = Utc::now();
+        let due: Vec<MyPlanet> = match store.lock() {
+            Ok(store) => store
+                .my_planets
+                .iter()
+                .filter(|p| !store.is_publishing(p.id))
+                .filter(|p| match self.retries.get(&p.id) {
+                    Some(retry) => now >= retry.next_at,
+                    None => is_due(p, now, threshold),
+                })
+                .cloned()
+                .collect(),
+            Err(_) => return,
+        };
+
+        for planet in due {
+            // 只在取 client 时短暂持有 IPFS 锁，publish_name 可能耗时数分钟
+            let Ok(client) = online_client(&ipfs).await else {
+                return;
+            };
+            // 列表是在循环前复制的，期间 planet_publish 可能已发布了新的 CID
+            // 发布前重新读取，避免用旧 CID 覆盖新的 IPNS 记录
+            let current = match store.lock() {
+                Ok(store) if !store.is_publishing(planet.id) => store
+                    .get_planet(planet.id)
+                    .and_then(|p| p.last_published_cid.clone()),
+                _ => None,
+            };
+            let Some(cid) = current.filter(|c| Some(c) == planet.last_published_cid.as_ref())
+            else {
+                continue;
+            };
+            let result = client
+                .publish_name(&planet.key_name(), &cid, settings.ipns_options())
+                .await
+                .map(|_| cid);
+
+            let outcome = match result {
+                Ok(cid) => {
+                    info!("Republished planet {} ({})", planet.id, cid);
+                    self.retries.remove(&planet.id);
+                    Ok(())
+                }
+                Err(e) => {
+                    let attempts = self.retries.get(&planet.id).map_or(0, |r| r.attempts) + 1;
+                    let delay = backoff(attempts, interval);
+                    warn!(
+                        "Failed to republish planet {} (attempt {}), retrying in {}s: {}",
+                        planet.id,
+                        attempts,
+                        delay.as_secs(),
+                        e
+                    );
+                    self.retries.insert(
+                        planet.id,
+                        Retry {
+                            attempts,
+                            next_at: Utc::now()
+                                + chrono::Duration::from_std(delay).unwrap_or_default(),
+                        },
+                    );
+                    Err(e.to_string())
+                }
+            };
+
+            if let Ok(mut store) = store.lock() {
+                if let Err(e) = store.record_republished(planet.id, outcome, app) {
+                    error!("Failed to record republish result: {}", e);
+                }
+                store.emit_state_changed(app);
+            }
+        }
+    }
+}
+
+/// Planet 的 IPNS 记录是否已超过 threshold 需要重新发布
+/// 从未发布过的 Planet 不参与
+pub fn is_due(planet: &MyPlanet, now: DateTime<Utc>, threshold: Duration) -> bool {
+    if planet.last_published_cid.is_none() || !planet.has_ipns_key() {
+        return false;
+    }
+    let last = match (planet.last_published, planet.last_republished) {
+        (Some(a), Some(b)) => a.max(b),
+        (a, b) => match a.or(b) {
+            Some(t) => t,
+            None => return true,
+        },
+    };
+    now.signed_duration_since(last)
+        .to_std()
+        .is_ok_and(|age| age >= threshold)
+}
+
+/// 第 attempts 次失败后的等待时间：指数增长，不超过 max
+pub fn backoff(attempts: u32, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    RETRY_BASE_DELAY.saturating_mul(factor).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(last_published: Option<DateTime<Utc>>) -> MyPlanet {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "name": "Test",
+            "about": "",
+            "created": "2024-01-01T00:00:00Z",
+            "ipns": "k51qzi5uqu5dexample",
+            "updated": "2024-01-01T00:00:00Z",
+            "template_name": "Plain",
+            "last_published": last_published,
+            "last_published_cid": "bafy-site",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_due_uses_latest_publish_time() {
+        let now = Utc::now();
+        let hour = Duration::from_secs(3600);
+
+        let mut p = planet(Some(now - chrono::Duration::hours(5)));
+        assert!(is_due(&p, now, hour));
+
+        p.last_republished = Some(now - chrono::Duration::minutes(10));
+        assert!(!is_due(&p, now, hour));
+
+        p.last_published_cid = None;
+        assert!(!is_due(&p, now, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let max = Duration::from_secs(600);
+        assert_eq!(backoff(1, max), Duration::from_secs(60));
+        assert_eq!(backoff(2, max), Duration::from_secs(120));
+        assert_eq!(backoff(3, max), Duration::from_secs(240));
+        assert_eq!(backoff(10, max), max);
+    }
+}
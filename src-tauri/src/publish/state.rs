@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ============================================================
+// 发布状态机
+// 对应原项目 MyPlanetModel.isPublishing / publishStartedAt
+// ============================================================
+
+/// 发布阶段
+/// Rendering → Hashing → Adding → PublishingIpns → Done，任一阶段出错进入 Failed
+/// CID 未变化时 Hashing 之后直接进入 Done
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishStage {
+    Rendering,
+    Hashing,
+    Adding,
+    PublishingIpns,
+    Done,
+    Failed,
+}
+
+impl PublishStage {
+    /// 是否仍在发布中
+    pub fn is_active(self) -> bool {
+        matches!(
+            self,
+            Self::Rendering | Self::Hashing | Self::Adding | Self::PublishingIpns
+        )
+    }
+}
+
+/// 单个 Planet 的发布状态
+/// 通过 app.emit("planet:publish-state-changed", &state) 推送
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishState {
+    pub planet_id: Uuid,
+    pub stage: PublishStage,
+    /// 本次发布开始时间
+    pub started_at: DateTime<Utc>,
+    /// 当前阶段开始时间
+    pub stage_started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PublishState {
+    pub fn new(planet_id: Uuid) -> Self {
+        let now = Utc::now();
+        Self {
+            planet_id,
+            stage: PublishStage::Rendering,
+            started_at: now,
+            stage_started_at: now,
+            error: None,
+        }
+    }
+
+    /// 进入下一阶段
+    pub fn advance(&mut self, stage: PublishStage) {
+        self.stage = stage;
+        self.stage_started_at = Utc::now();
+    }
+
+    /// 标记失败
+    pub fn fail(&mut self, error: String) {
+        self.advance(PublishStage::Failed);
+        self.error = Some(error);
+    }
+}
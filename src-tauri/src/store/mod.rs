@@ -1,10 +1,15 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 use anyhow::{anyhow, Result};
 use tracing::{debug, error, info};
 use tauri::{AppHandle, Emitter};
 
+use crate::ipfs::pinning::PinStatus;
+use crate::publish::prewarm::PrewarmReport;
+use crate::publish::state::{PublishStage, PublishState};
+
 use crate::models::planet::{MyPlanet, FollowingPlanet};
 use crate::models::article::{MyArticle, FollowingArticle};
 use crate::models::draft::Draft;
@@ -37,6 +42,12 @@ pub struct PlanetStore {
     pub selected_planet_articles: Vec<MyArticle>,
     pub selected_following_articles: Vec<FollowingArticle>,
     pub selected_article_id: Option<Uuid>,
+    /// 各 Planet 最近一次发布的状态（仅内存）
+    pub publish_states: HashMap<Uuid, PublishState>,
+    /// 各 Planet 最近一次网关预热的结果（仅内存）
+    pub prewarm_reports: HashMap<Uuid, PrewarmReport>,
+    /// 上次 GC 以来的发布次数（仅内存，供自动 GC 策略使用）
+    pub publishes_since_gc: u32,
 }
 
 /// 全局 PlanetStore 的类型别名
@@ -52,6 +63,9 @@ impl PlanetStore {
             selected_planet_articles: Vec::new(),
             selected_following_articles: Vec::new(),
             selected_article_id: None,
+            publish_states: HashMap::new(),
+            prewarm_reports: HashMap::new(),
+            publishes_since_gc: 0,
         }
     }
 
@@ -74,17 +88,9 @@ impl PlanetStore {
     // My Planet CRUD
     // ============================================================
 
-    /// 创建新 Planet
-    pub fn create_planet(
-        &mut self,
-        name: String,
-        about: String,
-        template_name: String,
-        app: &AppHandle,
-    ) -> Result<MyPlanet> {
-        let planet = MyPlanet::create(name, about, template_name, app)?;
-        self.my_planets.insert(0, planet.clone());
-        Ok(planet)
+    /// 加入新创建的 Planet（由 MyPlanet::create 生成）
+    pub fn insert_planet(&mut self, planet: MyPlanet) {
+        self.my_planets.insert(0, planet);
     }
 
     /// 获取 Planet（不可变引用）
@@ -109,10 +115,10 @@ impl PlanetStore {
     }
 
     /// 删除 Planet
+    /// IPNS key 由调用方通过 IpfsBackend 移除
     pub fn delete_planet(&mut self, planet_id: Uuid, app: &AppHandle) -> Result<()> {
         if let Some(idx) = self.my_planets.iter().position(|p| p.id == planet_id) {
-            let planet = &self.my_planets[idx];
-            planet.delete(app)?;
+            self.my_planets[idx].delete(app)?;
             self.my_planets.remove(idx);
             Ok(())
         } else {
@@ -120,6 +126,151 @@ impl PlanetStore {
         }
     }
 
+    /// 记录发布结果（不修改 updated 时间戳）
+    pub fn record_published(&mut self, planet_id: Uuid, cid: String, app: &AppHandle) -> Result<()> {
+        let planet = self.get_planet_mut(planet_id)
+            .ok_or_else(|| anyhow!("Planet not found: {}", planet_id))?;
+        planet.last_published = Some(chrono::Utc::now());
+        planet.last_published_cid = Some(cid);
+        planet.republish_error = None;
+        planet.save(app)?;
+        self.publishes_since_gc += 1;
+        Ok(())
+    }
+
+    /// 记录 IPNS 定期重新发布的结果
+    pub fn record_republished(
+        &mut self,
+        planet_id: Uuid,
+        result: Result<(), String>,
+        app: &AppHandle,
+    ) -> Result<()> {
+        let planet = self.get_planet_mut(planet_id)
+            .ok_or_else(|| anyhow!("Planet not found: {}", planet_id))?;
+        match result {
+            Ok(()) => {
+                planet.last_republished = Some(chrono::Utc::now());
+                planet.republish_error = None;
+            }
+            Err(e) => planet.republish_error = Some(e),
+        }
+        planet.save(app)
+    }
+
+    /// 记录 Filebase pin 状态
+    /// submitted 为 true 时是新提交的请求，替换记录的请求 id；
+    /// 否则只接受与记录的请求 id 一致的状态，忽略旧发布仍在轮询的结果
+    pub fn record_filebase_pin(
+        &mut self,
+        planet_id: Uuid,
+        status: &PinStatus,
+        submitted: bool,
+        app: &AppHandle,
+    ) -> Result<()> {
+        let planet = self.get_planet_mut(planet_id)
+            .ok_or_else(|| anyhow!("Planet not found: {}", planet_id))?;
+        if !submitted && planet.filebase_request_id.as_deref() != Some(status.requestid.as_str()) {
+            debug!("Ignoring stale Filebase pin status: {}", status.requestid);
+            return Ok(());
+        }
+        planet.filebase_request_id = Some(status.requestid.clone());
+        planet.filebase_pin_cid = Some(status.pin.cid.clone());
+        planet.filebase_pin_status = Some(status.status.clone());
+        planet.filebase_pin_status_retrieved = Some(chrono::Utc::now());
+        planet.save(app)
+    }
+
+    /// 记录远程 Pinning 服务的 pin 状态，submitted 的含义同 record_filebase_pin
+    pub fn record_remote_pin(
+        &mut self,
+        planet_id: Uuid,
+        service_name: &str,
+        status: &PinStatus,
+        submitted: bool,
+        app: &AppHandle,
+    ) -> Result<()> {
+        let planet = self.get_planet_mut(planet_id)
+            .ok_or_else(|| anyhow!("Planet not found: {}", planet_id))?;
+        let service = planet
+            .pinning_services
+            .iter_mut()
+            .find(|s| s.name == service_name)
+            .ok_or_else(|| anyhow!("Pinning service not found: {}", service_name))?;
+        if !submitted && service.request_id.as_deref() != Some(status.requestid.as_str()) {
+            debug!("Ignoring stale pin status from {}: {}", service_name, status.requestid);
+            return Ok(());
+        }
+        service.request_id = Some(status.requestid.clone());
+        service.pin_cid = Some(status.pin.cid.clone());
+        service.pin_status = Some(status.status.clone());
+        service.pin_status_retrieved = Some(chrono::Utc::now());
+        planet.save(app)
+    }
+
+    // ============================================================
+    // 发布状态
+    // ============================================================
+
+    /// 开始发布；同一 Planet 已在发布中时拒绝
+    pub fn begin_publish(&mut self, planet_id: Uuid, app: &AppHandle) -> Result<()> {
+        if self.get_planet(planet_id).is_none() {
+            return Err(anyhow!("Planet not found: {}", planet_id));
+        }
+        if self.is_publishing(planet_id) {
+            return Err(anyhow!("Planet {} is already being published", planet_id));
+        }
+        self.publish_states.insert(planet_id, PublishState::new(planet_id));
+        self.emit_publish_state_changed(planet_id, app);
+        Ok(())
+    }
+
+    /// 更新发布阶段
+    pub fn set_publish_stage(&mut self, planet_id: Uuid, stage: PublishStage, app: &AppHandle) {
+        if let Some(state) = self.publish_states.get_mut(&planet_id) {
+            if state.stage != stage {
+                state.advance(stage);
+                self.emit_publish_state_changed(planet_id, app);
+            }
+        }
+    }
+
+    /// 标记发布失败
+    pub fn fail_publish(&mut self, planet_id: Uuid, error: String, app: &AppHandle) {
+        if let Some(state) = self.publish_states.get_mut(&planet_id) {
+            state.fail(error);
+            self.emit_publish_state_changed(planet_id, app);
+        }
+    }
+
+    /// Planet 是否正在发布
+    pub fn is_publishing(&self, planet_id: Uuid) -> bool {
+        self.publish_states
+            .get(&planet_id)
+            .is_some_and(|s| s.stage.is_active())
+    }
+
+    /// 是否有任一 Planet 正在发布
+    pub fn any_publishing(&self) -> bool {
+        self.publish_states.values().any(|s| s.stage.is_active())
+    }
+
+    /// 记录网关预热结果并通知前端
+    pub fn record_prewarm(&mut self, report: PrewarmReport, app: &AppHandle) {
+        if let Err(e) = app.emit("planet:prewarm-finished", &report) {
+            error!("Failed to emit prewarm report: {}", e);
+        }
+        self.prewarm_reports.insert(report.planet_id, report);
+    }
+
+    /// 尚未拥有 IPNS key 的 Planet
+    pub fn planets_without_keys(&self) -> Vec<MyPlanet> {
+        self.my_planets
+            .iter()
+            .filter(|p| !p.has_ipns_key())
+            .cloned()
+            .collect()
+    }
+
     // ============================================================
     // Article CRUD
     // ============================================================
@@ -297,6 +448,8 @@ pub struct PlanetStoreSnapshot {
     pub my_planets: Vec<MyPlanet>,
     pub following_planets: Vec<FollowingPlanet>,
     pub selected_view: Option<SelectedView>,
+    pub publish_states: HashMap<Uuid, PublishState>,
+    pub prewarm_reports: HashMap<Uuid, PrewarmReport>,
 }
 
 impl PlanetStore {
@@ -305,6 +458,8 @@ impl PlanetStore {
             my_planets: self.my_planets.clone(),
             following_planets: self.following_planets.clone(),
             selected_view: self.selected_view.clone(),
+            publish_states: self.publish_states.clone(),
+            prewarm_reports: self.prewarm_reports.clone(),
         }
     }
 
@@ -315,4 +470,13 @@ impl PlanetStore {
             error!("Failed to emit planet state: {}", e);
         }
     }
-}
\ No newline at end of file
+
+    /// 通知前端单个 Planet 的发布状态变化
+    pub fn emit_publish_state_changed(&self, planet_id: Uuid, app: &AppHandle) {
+        if let Some(state) = self.publish_states.get(&planet_id) {
+            if let Err(e) = app.emit("planet:publish-state-changed", state) {
+                error!("Failed to emit publish state: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use pulldown_cmark::{html, Options, Parser};
+use std::fs;
+use std::path::Path;
+use tracing::{debug, info};
+
+use crate::models::article::{MyArticle, PublicArticle};
+use crate::models::planet::{MyPlanet, PublicPlanet};
+
+// ============================================================
+// 站点渲染
+// 对应原项目 Template.swift（当前仅内置 Plain 模板）
+// ============================================================
+
+/// 将 Markdown 渲染为 HTML
+pub fn render_markdown(content: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let parser = Parser::new_ext(content, options);
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+/// 渲染 Planet 的公开站点到 output 目录
+///
+/// 目录结构：
+/// - index.html
+/// - planet.json
+/// - {article_id}/index.html
+/// - {article_id}/article.json
+pub fn render_planet(planet: &MyPlanet, articles: &[MyArticle], output: &Path) -> Result<()> {
+    info!("Rendering planet {} to {:?}", planet.id, output);
+
+    // 清空旧的输出，避免已删除文章残留在站点中
+    if output.exists() {
+        fs::remove_dir_all(output)?;
+    }
+    fs::create_dir_all(output)?;
+
+    let public_articles: Vec<PublicArticle> = articles
+        .iter()
+        .map(|article| {
+            let mut public = PublicArticle::from(article);
+            public.content_rendered = Some(render_markdown(&article.content));
+            public
+        })
+        .collect();
+
+    for article in &public_articles {
+        let article_dir = output.join(article.id.to_string());
+        fs::create_dir_all(&article_dir)?;
+        fs::write(article_dir.join("index.html"), article_page(planet, article))?;
+        fs::write(
+            article_dir.join("article.json"),
+            serde_json::to_string_pretty(article)?,
+        )?;
+        debug!("Rendered article: {} ({})", article.title, article.id);
+    }
+
+    fs::write(output.join("index.html"), index_page(planet, &public_articles))?;
+
+    let mut public_planet = PublicPlanet::from(planet);
+    public_planet.articles = public_articles;
+    fs::write(
+        output.join("planet.json"),
+        serde_json::to_string_pretty(&public_planet)?,
+    )?;
+
+    info!("Rendered {} article(s) for planet {}", articles.len(), planet.id);
+    Ok(())
+}
+
+// ============================================================
+// Plain 模板
+// ============================================================
+
+fn index_page(planet: &MyPlanet, articles: &[PublicArticle]) -> String {
+    let items: String = articles
+        .iter()
+        .map(|article| {
+            format!(
+                "<li><a href=\"./{}/\">{}</a> <time>{}</time></li>\n",
+                article.id,
+                escape_html(&article.title),
+                article.created.format("%Y-%m-%d"),
+            )
+        })
+        .collect();
+
+    let body = format!(
+        "<header><h1>{}</h1><p>{}</p></header>\n<ul class=\"articles\">\n{}</ul>",
+        escape_html(&planet.name),
+        escape_html(&planet.about),
+        items,
+    );
+    layout(&planet.name, &body, planet.do_not_index.unwrap_or(false))
+}
+
+fn article_page(planet: &MyPlanet, article: &PublicArticle) -> String {
+    let body = format!(
+        "<nav><a href=\"../\">{}</a></nav>\n<article><h1>{}</h1><time>{}</time>\n{}</article>",
+        escape_html(&planet.name),
+        escape_html(&article.title),
+        article.created.format("%Y-%m-%d"),
+        article.content_rendered.as_deref().unwrap_or_default(),
+    );
+    let title = format!("{} - {}", article.title, planet.name);
+    layout(&title, &body, planet.do_not_index.unwrap_or(false))
+}
+
+fn layout(title: &str, body: &str, do_not_index: bool) -> String {
+    let robots = if do_not_index {
+        "<meta name=\"robots\" content=\"noindex\">\n"
+    } else {
+        ""
+    };
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         {}<title>{}</title>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        robots,
+        escape_html(title),
+        body,
+    )
+}
+
+/// 转义 HTML 特殊字符
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -10,7 +10,15 @@ import type { MyArticle } from './types/planet'
 
 function App() {
   // 全局状态
-  const { myPlanets, loading: planetsLoading, createPlanet, deletePlanet } = usePlanetStore()
+  const {
+    myPlanets,
+    publishStates,
+    prewarmReports,
+    loading: planetsLoading,
+    createPlanet,
+    deletePlanet,
+    publishPlanet,
+  } = usePlanetStore()
 
   // 选中状态
   const [selectedPlanetId, setSelectedPlanetId] = useState<string | null>(null)
@@ -64,6 +72,34 @@ function App() {
     [deleteArticle]
   )
 
+  // 发布当前 Planet（进度通过 publishStates 事件更新）
+  const handlePublish = useCallback(async () => {
+    if (!selectedPlanetId) return
+    try {
+      await publishPlanet(selectedPlanetId)
+    } catch (e) {
+      console.error('Publish failed:', e)
+    }
+  }, [selectedPlanetId, publishPlanet])
+
+  const selectedPlanet = myPlanets.find((p) => p.id === selectedPlanetId)
+  const remotePinStatuses = [
+    ...(selectedPlanet?.filebase_pin_status
+      ? [{ name: 'Filebase', status: selectedPlanet.filebase_pin_status }]
+      : []),
+    ...(selectedPlanet?.pinning_services ?? [])
+      .filter((s) => s.pin_status)
+      .map((s) => ({ name: s.name, status: s.pin_status! })),
+  ]
+
+  const selectedPublishStage = selectedPlanetId
+    ? publishStates[selectedPlanetId]?.stage
+    : undefined
+  const publishing =
+    selectedPublishStage !== undefined &&
+    selectedPublishStage !== 'done' &&
+    selectedPublishStage !== 'failed'
+
   if (planetsLoading) {
     return (
       <div className="h-screen flex items-center justify-center bg-white dark:bg-gray-950">
@@ -77,6 +113,7 @@ function App() {
       {/* 左侧：Planet 列表 */}
       <Sidebar
         planets={myPlanets}
+        publishStates={publishStates}
         selectedPlanetId={selectedPlanetId}
         onSelectPlanet={setSelectedPlanetId}
         onCreatePlanet={() => setShowNewPlanet(true)}
@@ -89,6 +126,11 @@ function App() {
           selectedArticleId={selectedArticle?.id || null}
           onSelectArticle={handleSelectArticle}
           onCreateArticle={() => setShowNewArticle(true)}
+          onPublish={handlePublish}
+          publishing={publishing}
+          pinStatuses={remotePinStatuses}
+          republishError={selectedPlanet?.republish_error}
+          prewarmReport={selectedPlanetId ? prewarmReports[selectedPlanetId] : undefined}
           loading={articlesLoading}
         />
       )}
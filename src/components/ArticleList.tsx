@@ -1,10 +1,15 @@
-import type { MyArticle } from '../types/planet'
+import type { MyArticle, PrewarmReport } from '../types/planet'
 
 interface ArticleListProps {
   articles: MyArticle[]
   selectedArticleId: string | null
   onSelectArticle: (id: string) => void
   onCreateArticle: () => void
+  onPublish: () => void
+  publishing: boolean
+  pinStatuses?: { name: string; status: string }[]
+  prewarmReport?: PrewarmReport
+  republishError?: string
   loading: boolean
 }
 
@@ -13,6 +18,11 @@ export function ArticleList({
   selectedArticleId,
   onSelectArticle,
   onCreateArticle,
+  onPublish,
+  publishing,
+  pinStatuses = [],
+  prewarmReport,
+  republishError,
   loading,
 }: ArticleListProps) {
   if (loading) {
@@ -27,15 +37,47 @@ export function ArticleList({
     <div className="w-72 border-r border-gray-200 dark:border-gray-700 flex flex-col h-full bg-white dark:bg-gray-950">
       {/* 头部 */}
       <div className="p-4 border-b border-gray-200 dark:border-gray-700 flex items-center justify-between">
-        <h2 className="text-sm font-semibold text-gray-600 dark:text-gray-400">
-          Articles ({articles.length})
-        </h2>
-        <button
-          onClick={onCreateArticle}
-          className="px-3 py-1 text-xs bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
-        >
-          New
-        </button>
+        <div>
+          <h2 className="text-sm font-semibold text-gray-600 dark:text-gray-400">
+            Articles ({articles.length})
+          </h2>
+          {pinStatuses.map((pin) => (
+            <div key={pin.name} className="text-xs text-gray-400">
+              {pin.name}: {pin.status}
+            </div>
+          ))}
+          {republishError && (
+            <div className="text-xs text-red-500" title={republishError}>
+              IPNS republish failed
+            </div>
+          )}
+          {prewarmReport && (
+            <div
+              className="text-xs text-gray-400"
+              title={prewarmReport.results
+                .map((r) => `${r.ok ? '✓' : '✗'} ${r.url}${r.error ? ` (${r.error})` : ''}`)
+                .join('\n')}
+            >
+              Prewarmed: {prewarmReport.results.filter((r) => r.ok).length}/
+              {prewarmReport.results.length}
+            </div>
+          )}
+        </div>
+        <div className="flex gap-2">
+          <button
+            onClick={onPublish}
+            disabled={publishing}
+            className="px-3 py-1 text-xs border border-blue-500 text-blue-500 rounded hover:bg-blue-50 dark:hover:bg-blue-900/20 disabled:opacity-50 transition-colors"
+          >
+            {publishing ? 'Publishing...' : 'Publish'}
+          </button>
+          <button
+            onClick={onCreateArticle}
+            className="px-3 py-1 text-xs bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
+          >
+            New
+          </button>
+        </div>
       </div>
 
       {/* 文章列表 */}
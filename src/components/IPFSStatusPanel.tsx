@@ -1,5 +1,6 @@
 import { useState } from 'react'
-import { useIPFS } from '../hooks/useIPFS'
+import { useIPFS, useIpfsMetrics } from '../hooks/useIPFS'
+import type { IpfsMetricsSample } from '../types/ipfs'
 
 /**
  * IPFS 状态面板
@@ -10,12 +11,14 @@ import { useIPFS } from '../hooks/useIPFS'
  * - Local Gateway 地址
  * - Repo Size
  * - Peers 数量
+ * - 带宽速率与最近一小时曲线
  * - IPFS Version
- * - Launch/Shutdown 切换按钮
+ * - Launch/Shutdown 切换按钮（外部节点不可关闭）
  * - GC 按钮
  */
 export function IPFSStatusPanel() {
   const { state, loading, launch, shutdown, gc, refresh } = useIPFS()
+  const { samples, latest } = useIpfsMetrics()
   const [showGCConfirm, setShowGCConfirm] = useState(false)
   const [gcResult, setGcResult] = useState<string | null>(null)
 
@@ -27,7 +30,7 @@ export function IPFSStatusPanel() {
     )
   }
 
-  const gatewayUrl = `http://127.0.0.1:${state.gateway_port}`
+  const gatewayUrl = state.gateway_url ?? `http://127.0.0.1:${state.gateway_port}`
 
   /** 格式化字节 */
   const formatBytes = (bytes: number): string => {
@@ -81,6 +84,11 @@ export function IPFSStatusPanel() {
           <span className="text-gray-500 dark:text-gray-400">Repo Size</span>
           <span className="text-gray-700 dark:text-gray-300">
             {state.repo_size !== null ? formatBytes(state.repo_size) : '—'}
+            {state.repo_size !== null && state.storage_max
+              ? ` / ${formatBytes(state.storage_max)} (${Math.round(
+                  (state.repo_size * 100) / state.storage_max
+                )}%)`
+              : ''}
           </span>
         </div>
 
@@ -94,6 +102,17 @@ export function IPFSStatusPanel() {
           </span>
         </div>
 
+        {/* Bandwidth */}
+        <div className="flex justify-between items-center">
+          <span className="text-gray-500 dark:text-gray-400">Bandwidth</span>
+          <span className="text-gray-700 dark:text-gray-300 text-xs">
+            {state.online && latest
+              ? `↓ ${formatBytes(latest.rate_in)}/s ↑ ${formatBytes(latest.rate_out)}/s`
+              : '—'}
+          </span>
+        </div>
+        {state.online && samples.length > 1 && <BandwidthGraph samples={samples} />}
+
         {/* IPFS Version */}
         <div className="flex justify-between items-center">
           <span className="text-gray-500 dark:text-gray-400">IPFS Version</span>
@@ -166,6 +185,8 @@ export function IPFSStatusPanel() {
                 className="sr-only peer"
                 checked={state.online}
                 onChange={handleToggle}
+                disabled={state.online && !state.managed}
+                title={!state.managed ? 'External IPFS node' : undefined}
               />
               <div className="w-9 h-5 bg-gray-200 peer-focus:outline-none rounded-full peer dark:bg-gray-600
                 peer-checked:after:translate-x-full peer-checked:after:border-white
@@ -207,4 +228,26 @@ export function IPFSStatusPanel() {
       )}
     </div>
   )
-}
\ No newline at end of file
+}
+
+/** 带宽曲线（入站蓝色、出站绿色） */
+function BandwidthGraph({ samples }: { samples: IpfsMetricsSample[] }) {
+  const width = 264
+  const height = 40
+  const max = Math.max(1, ...samples.map((s) => Math.max(s.rate_in, s.rate_out)))
+  const points = (rate: (s: IpfsMetricsSample) => number) =>
+    samples
+      .map((s, i) => {
+        const x = (i / (samples.length - 1)) * width
+        const y = height - (rate(s) / max) * height
+        return `${x.toFixed(1)},${y.toFixed(1)}`
+      })
+      .join(' ')
+
+  return (
+    <svg width={width} height={height} className="block">
+      <polyline points={points((s) => s.rate_in)} fill="none" stroke="#3b82f6" strokeWidth={1} />
+      <polyline points={points((s) => s.rate_out)} fill="none" stroke="#22c55e" strokeWidth={1} />
+    </svg>
+  )
+}
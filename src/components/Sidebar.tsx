@@ -1,8 +1,18 @@
 import { useState } from 'react'
-import type { MyPlanet } from '../types/planet'
+import type { MyPlanet, PublishState } from '../types/planet'
+
+/** 发布阶段的显示文本 */
+const stageLabels: Record<string, string> = {
+  rendering: 'Rendering...',
+  hashing: 'Computing CID...',
+  adding: 'Adding to IPFS...',
+  publishing_ipns: 'Publishing to IPNS...',
+  failed: 'Publish failed',
+}
 
 interface SidebarProps {
   planets: MyPlanet[]
+  publishStates: Record<string, PublishState>
   selectedPlanetId: string | null
   onSelectPlanet: (id: string) => void
   onCreatePlanet: () => void
@@ -10,6 +20,7 @@ interface SidebarProps {
 
 export function Sidebar({
   planets,
+  publishStates,
   selectedPlanetId,
   onSelectPlanet,
   onCreatePlanet,
@@ -37,7 +48,13 @@ export function Sidebar({
             暂无 Planet，点击 + 创建
           </div>
         ) : (
-          planets.map((planet) => (
+          planets.map((planet) => {
+            const publishState = publishStates[planet.id]
+            const publishing =
+              publishState !== undefined &&
+              publishState.stage !== 'done' &&
+              publishState.stage !== 'failed'
+            return (
             <div
               key={planet.id}
               onClick={() => onSelectPlanet(planet.id)}
@@ -57,12 +74,18 @@ export function Sidebar({
                     {planet.name}
                   </div>
                   <div className="text-xs text-gray-500 dark:text-gray-400 truncate">
-                    {planet.about || 'No description'}
+                    {publishState && stageLabels[publishState.stage]
+                      ? <span title={publishState.error}>{stageLabels[publishState.stage]}</span>
+                      : planet.about || 'No description'}
                   </div>
                 </div>
+                {publishing && (
+                  <div className="w-4 h-4 border-2 border-blue-500 border-t-transparent rounded-full animate-spin" />
+                )}
               </div>
             </div>
-          ))
+            )
+          })
         )}
       </div>
     </div>
@@ -1,16 +1,33 @@
 import { useState, useEffect, useCallback } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
-import type { IpfsStateSnapshot } from '../types/ipfs'
+import type {
+  IpfsAddProgress,
+  IpfsLogFilter,
+  IpfsLogLine,
+  IpfsMetricsSample,
+  IpfsOfflineEvent,
+  IpfsPeer,
+  IpfsPeeringPeer,
+  IpfsPinEntry,
+  IpfsPinProgress,
+  IpfsRepoStat,
+  IpfsStateSnapshot,
+  PinType,
+} from '../types/ipfs'
 
 /** 默认初始状态 */
 const defaultState: IpfsStateSnapshot = {
   online: false,
   is_operating: false,
+  lifecycle: { state: 'uninitialized' },
   api_port: 5981,
   gateway_port: 18181,
   swarm_port: 4001,
   repo_size: null,
+  storage_max: null,
+  managed: true,
+  gateway_url: null,
   server_info: null,
   error_message: null,
 }
@@ -20,13 +37,15 @@ const defaultState: IpfsStateSnapshot = {
  *
  * 功能：
  * 1. 监听后端推送的 "ipfs:state-changed" 事件，自动更新状态
- * 2. 提供 setup / launch / shutdown / gc / refresh 操作方法
+ * 2. 监听 "ipfs:offline" 事件，记录 daemon 最近一次意外离线
+ * 3. 提供 setup / launch / shutdown / gc / refresh 操作方法
  *
  * 对应原项目 SwiftUI 中的 @EnvironmentObject IPFSState
  */
 export function useIPFS() {
   const [state, setState] = useState<IpfsStateSnapshot>(defaultState)
   const [loading, setLoading] = useState(true)
+  const [lastOffline, setLastOffline] = useState<IpfsOfflineEvent | null>(null)
 
   // 初始加载：获取当前状态
   useEffect(() => {
@@ -52,6 +71,18 @@ export function useIPFS() {
     }
   }, [])
 
+  // 监听 daemon 意外离线（后端会自动重启）
+  useEffect(() => {
+    const unlisten = listen<IpfsOfflineEvent>('ipfs:offline', (event) => {
+      console.warn('IPFS daemon went offline:', event.payload)
+      setLastOffline(event.payload)
+    })
+
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
   // 操作方法
   const setup = useCallback(async () => {
     try {
@@ -95,13 +126,253 @@ export function useIPFS() {
     }
   }, [])
 
+  /** 修复损坏的仓库（保留 keystore），返回备份目录 */
+  const repair = useCallback(async (): Promise<string | null> => {
+    try {
+      return await invoke<string>('ipfs_repair_repo')
+    } catch (e) {
+      console.error('IPFS repair failed:', e)
+      return null
+    }
+  }, [])
+
+  /** 切换网络配置方案，daemon 重启后生效 */
+  const setNetworkProfile = useCallback(async (name: string): Promise<boolean> => {
+    try {
+      await invoke('ipfs_set_network_profile', { name })
+      return true
+    } catch (e) {
+      console.error('Failed to set network profile:', e)
+      return false
+    }
+  }, [])
+
   return {
     state,
     loading,
+    lastOffline,
     setup,
     launch,
     shutdown,
     gc,
     refresh,
+    repair,
+    setNetworkProfile,
   }
 }
+
+/** 前端保留的实时日志行数 */
+const MAX_LIVE_LOGS = 1000
+
+/**
+ * Kubo daemon 日志 Hook
+ *
+ * 初始加载后端缓冲中的日志，之后通过 "ipfs:log" 事件实时追加
+ */
+export function useIpfsLogs(filter?: IpfsLogFilter) {
+  const [logs, setLogs] = useState<IpfsLogLine[]>([])
+
+  useEffect(() => {
+    invoke<IpfsLogLine[]>('ipfs_get_logs', { filter: filter ?? null })
+      .then(setLogs)
+      .catch((e) => console.error('Failed to get IPFS logs:', e))
+  }, [filter?.stream, filter?.contains, filter?.since, filter?.limit])
+
+  useEffect(() => {
+    const unlisten = listen<IpfsLogLine>('ipfs:log', (event) => {
+      const line = event.payload
+      if (filter?.stream && line.stream !== filter.stream) return
+      if (
+        filter?.contains &&
+        !line.line.toLowerCase().includes(filter.contains.toLowerCase())
+      ) {
+        return
+      }
+      setLogs((prev) => [...prev, line].slice(-MAX_LIVE_LOGS))
+    })
+
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [filter?.stream, filter?.contains])
+
+  const saveLogs = useCallback(async (path?: string): Promise<string | null> => {
+    try {
+      return await invoke<string>('ipfs_save_logs', { path: path ?? null })
+    } catch (e) {
+      console.error('Failed to save IPFS logs:', e)
+      return null
+    }
+  }, [])
+
+  return { logs, saveLogs }
+}
+
+/** 前端保留的指标采样数，与后端一致（约一小时） */
+const MAX_METRICS_SAMPLES = 360
+
+/**
+ * 带宽与 Peer 指标 Hook
+ *
+ * 初始加载后端历史，之后通过 "ipfs:metrics" 事件实时追加
+ */
+export function useIpfsMetrics() {
+  const [samples, setSamples] = useState<IpfsMetricsSample[]>([])
+
+  useEffect(() => {
+    invoke<IpfsMetricsSample[]>('ipfs_get_metrics')
+      .then(setSamples)
+      .catch((e) => console.error('Failed to get IPFS metrics:', e))
+  }, [])
+
+  useEffect(() => {
+    const unlisten = listen<IpfsMetricsSample>('ipfs:metrics', (event) => {
+      setSamples((prev) => [...prev, event.payload].slice(-MAX_METRICS_SAMPLES))
+    })
+
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  return { samples, latest: samples[samples.length - 1] ?? null }
+}
+
+/**
+ * Swarm Peer 查看 Hook
+ *
+ * 按需刷新已连接 peer 和 Peering 配置，连接/断开后自动刷新
+ */
+export function useSwarmPeers() {
+  const [peers, setPeers] = useState<IpfsPeer[]>([])
+  const [peering, setPeering] = useState<IpfsPeeringPeer[]>([])
+  const [error, setError] = useState<string | null>(null)
+
+  const refresh = useCallback(async () => {
+    try {
+      const [peers, peering] = await Promise.all([
+        invoke<IpfsPeer[]>('ipfs_swarm_peers'),
+        invoke<IpfsPeeringPeer[]>('ipfs_peering_peers'),
+      ])
+      setPeers(peers)
+      setPeering(peering)
+      setError(null)
+    } catch (e) {
+      setError(String(e))
+    }
+  }, [])
+
+  const connect = useCallback(
+    async (addr: string) => {
+      await invoke<string[]>('ipfs_swarm_connect', { addr })
+      await refresh()
+    },
+    [refresh]
+  )
+
+  const disconnect = useCallback(
+    async (addr: string) => {
+      await invoke<string[]>('ipfs_swarm_disconnect', { addr })
+      await refresh()
+    },
+    [refresh]
+  )
+
+  return { peers, peering, error, refresh, connect, disconnect }
+}
+
+/**
+ * 本地 pin 管理：按类型列出、pin / unpin、检查是否已 pin
+ * pin 进度按 CID 记录在 progress 中
+ */
+export function useLocalPins(pinType: PinType = 'recursive') {
+  const [pins, setPins] = useState<IpfsPinEntry[]>([])
+  const [progress, setProgress] = useState<Record<string, IpfsPinProgress>>({})
+  const [error, setError] = useState<string | null>(null)
+
+  const refresh = useCallback(async () => {
+    try {
+      setPins(await invoke<IpfsPinEntry[]>('ipfs_pin_ls', { pinType }))
+      setError(null)
+    } catch (e) {
+      setError(String(e))
+    }
+  }, [pinType])
+
+  useEffect(() => {
+    refresh()
+  }, [refresh])
+
+  useEffect(() => {
+    const unlisten = listen<IpfsPinProgress>('ipfs:pin-progress', (event) => {
+      setProgress((prev) => ({ ...prev, [event.payload.cid]: event.payload }))
+    })
+
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  const pin = useCallback(
+    async (cid: string) => {
+      await invoke('ipfs_pin_add', { cid })
+      await refresh()
+    },
+    [refresh]
+  )
+
+  const unpin = useCallback(
+    async (cid: string) => {
+      await invoke('ipfs_pin_rm', { cid })
+      await refresh()
+    },
+    [refresh]
+  )
+
+  const check = useCallback(
+    (cid: string) => invoke<IpfsPinEntry | null>('ipfs_pin_check', { cid }),
+    []
+  )
+
+  return { pins, progress, error, refresh, pin, unpin, check }
+}
+
+/** 仓库与 Kubo 版本信息，daemon 离线时同样可用 */
+export function useRepoStat() {
+  const [stat, setStat] = useState<IpfsRepoStat | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  const refresh = useCallback(async () => {
+    try {
+      setStat(await invoke<IpfsRepoStat>('ipfs_repo_stat'))
+      setError(null)
+    } catch (e) {
+      setError(String(e))
+    }
+  }, [])
+
+  useEffect(() => {
+    refresh()
+  }, [refresh])
+
+  return { stat, error, refresh }
+}
+
+/**
+ * 目录 add 进度（发布时由 "ipfs:add-progress" 推送），按本地目录记录
+ */
+export function useAddProgress() {
+  const [progress, setProgress] = useState<Record<string, IpfsAddProgress>>({})
+
+  useEffect(() => {
+    const unlisten = listen<IpfsAddProgress>('ipfs:add-progress', (event) => {
+      setProgress((prev) => ({ ...prev, [event.payload.path]: event.payload }))
+    })
+
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  return progress
+}
@@ -6,10 +6,15 @@ import type {
   MyArticle,
   Draft,
   PlanetStoreSnapshot,
+  PrewarmReport,
+  PublishResult,
+  PublishState,
 } from '../types/planet'
 
 export function usePlanetStore() {
   const [myPlanets, setMyPlanets] = useState<MyPlanet[]>([])
+  const [publishStates, setPublishStates] = useState<Record<string, PublishState>>({})
+  const [prewarmReports, setPrewarmReports] = useState<Record<string, PrewarmReport>>({})
   const [loading, setLoading] = useState(true)
 
   // 初始加载
@@ -17,6 +22,8 @@ export function usePlanetStore() {
     invoke<PlanetStoreSnapshot>('planet_get_state')
       .then((state) => {
         setMyPlanets(state.my_planets)
+        setPublishStates(state.publish_states)
+        setPrewarmReports(state.prewarm_reports)
       })
       .catch(console.error)
       .finally(() => setLoading(false))
@@ -28,6 +35,40 @@ export function usePlanetStore() {
       'planet:state-changed',
       (event) => {
         setMyPlanets(event.payload.my_planets)
+        setPublishStates(event.payload.publish_states)
+        setPrewarmReports(event.payload.prewarm_reports)
+      }
+    )
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  // 监听发布进度事件
+  useEffect(() => {
+    const unlisten = listen<PublishState>(
+      'planet:publish-state-changed',
+      (event) => {
+        setPublishStates((prev) => ({
+          ...prev,
+          [event.payload.planet_id]: event.payload,
+        }))
+      }
+    )
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  // 监听网关预热完成事件
+  useEffect(() => {
+    const unlisten = listen<PrewarmReport>(
+      'planet:prewarm-finished',
+      (event) => {
+        setPrewarmReports((prev) => ({
+          ...prev,
+          [event.payload.planet_id]: event.payload,
+        }))
       }
     )
     return () => {
@@ -65,12 +106,32 @@ export function usePlanetStore() {
     []
   )
 
+  // 发布 Planet 到 IPFS/IPNS
+  const publishPlanet = useCallback(async (planetId: string) => {
+    return invoke<PublishResult>('planet_publish', { planetId })
+  }, [])
+
+  // 导出最近一次发布的内容为 CAR 归档，返回归档目录
+  const exportPlanetCar = useCallback(async (planetId: string, targetDir: string) => {
+    return invoke<string>('planet_export_car', { planetId, targetDir })
+  }, [])
+
+  // 从 CAR 归档导入 Planet
+  const importPlanetCar = useCallback(async (path: string) => {
+    return invoke<MyPlanet>('planet_import_car', { path })
+  }, [])
+
   return {
     myPlanets,
+    publishStates,
+    prewarmReports,
     loading,
     createPlanet,
     deletePlanet,
     updatePlanet,
+    publishPlanet,
+    exportPlanetCar,
+    importPlanetCar,
   }
 }
 
@@ -2,14 +2,31 @@
 export interface IpfsStateSnapshot {
     online: boolean
     is_operating: boolean
+    lifecycle: DaemonLifecycle
     api_port: number
     gateway_port: number
     swarm_port: number
     repo_size: number | null
+    /** Datastore.StorageMax（字节） */
+    storage_max: number | null
+    /** false 表示使用外部 Kubo 节点，不能由应用关闭 */
+    managed: boolean
+    /** 外部节点的网关地址 */
+    gateway_url: string | null
     server_info: ServerInfo | null
     error_message: string | null
   }
   
+  /** daemon 生命周期 — 与 Rust DaemonLifecycle 一一对应 */
+  export type DaemonLifecycle =
+    | { state: 'uninitialized' }
+    | { state: 'setting_up' }
+    | { state: 'starting' }
+    | { state: 'online' }
+    | { state: 'stopping' }
+    | { state: 'offline' }
+    | { state: 'failed'; message: string }
+
   /** 服务器信息 — 与 Rust ServerInfo 一一对应 */
   export interface ServerInfo {
     host_name: string
@@ -17,4 +34,113 @@ export interface IpfsStateSnapshot {
     ipfs_peer_id: string
     ipfs_version: string
     ipfs_peer_count: number
-  }
\ No newline at end of file
+  }
+
+  /** IPNS 发布结果 — 与 Rust IpfsPublished 一一对应 */
+  export interface IpfsPublished {
+    Name: string
+    Value: string
+  }
+
+  /** daemon 意外离线事件 — 与 Rust IpfsOfflineEvent 一一对应 */
+  export interface IpfsOfflineEvent {
+    reason: 'exited' | 'health_check_failed'
+    exit_code?: number
+    stderr_tail: string[]
+  }
+
+  /** daemon 输出行 — 与 Rust IpfsLogLine 一一对应 */
+  export interface IpfsLogLine {
+    timestamp: string
+    stream: 'stdout' | 'stderr'
+    line: string
+  }
+
+  /** ipfs_get_logs 过滤条件 — 与 Rust LogFilter 一一对应 */
+  export interface IpfsLogFilter {
+    stream?: 'stdout' | 'stderr'
+    contains?: string
+    since?: string
+    limit?: number
+  }
+
+  /** 带宽与 Peer 采样 — 与 Rust IpfsMetricsSample 一一对应 */
+  export interface IpfsMetricsSample {
+    timestamp: string
+    rate_in: number
+    rate_out: number
+    total_in: number
+    total_out: number
+    peer_count: number
+  }
+
+  /** 已连接的 peer — 与 Rust IpfsPeer 一一对应（verbose 模式） */
+  export interface IpfsPeer {
+    Addr?: string
+    Peer?: string
+    Latency?: string
+    Muxer?: string
+    /** 1 = inbound, 2 = outbound */
+    Direction?: number
+  }
+
+  /** Peering.Peers 配置项 — 与 Rust IpfsPeeringPeer 一一对应 */
+  export interface IpfsPeeringPeer {
+    ID: string
+    Addrs: string[]
+    connected: boolean
+  }
+
+  /** pin 归属 — 与 Rust PinOwner 一一对应 */
+  export type PinOwner =
+    | { kind: 'planet'; planet_id: string; name: string }
+    | { kind: 'article'; planet_id: string; article_id: string; title: string }
+    | { kind: 'following_planet'; planet_id: string; name: string }
+
+  export type PinType = 'recursive' | 'direct' | 'indirect' | 'all'
+
+  /** 本地 pin — 与 Rust IpfsPinEntry 一一对应 */
+  export interface IpfsPinEntry {
+    cid: string
+    pin_type: string
+    owner?: PinOwner
+  }
+
+  /** "ipfs:pin-progress" 事件 payload */
+  export interface IpfsPinProgress {
+    cid: string
+    blocks: number
+    done: boolean
+  }
+
+  /** "ipfs:add-progress" 事件 payload（daemon 在线时通过 HTTP 流式 add） */
+  export interface IpfsAddProgress {
+    path: string
+    bytes: number
+    total: number
+  }
+
+  /** 仓库状态 — 与 Rust IpfsRepoState 一一对应 */
+  export interface IpfsRepoState {
+    RepoSize: number
+    StorageMax: number
+    NumObjects: number
+    RepoPath: string
+    Version: string
+  }
+
+  /** Kubo 版本 — 与 Rust IpfsVersion 一一对应 */
+  export interface IpfsVersion {
+    Version: string
+    Commit: string
+    Repo: string
+    System: string
+    Golang: string
+  }
+
+  /** ipfs_repo_stat 返回值；online 为 false 时来自 Kubo CLI */
+  export interface IpfsRepoStat {
+    online: boolean
+    repo: IpfsRepoState
+    version: IpfsVersion
+  }
@@ -2,6 +2,8 @@
 // Planet 相关类型定义
 // ============================================================
 
+import type { IpfsPublished } from './ipfs'
+
 export interface MyPlanet {
     id: string
     name: string
@@ -20,6 +22,26 @@ export interface MyPlanet {
     telegram_username?: string
     mastodon_username?: string
     discord_link?: string
+    filebase_enabled?: boolean
+    filebase_pin_name?: string
+    filebase_api_token?: string
+    filebase_request_id?: string
+    filebase_pin_cid?: string
+    filebase_pin_status?: string
+    filebase_pin_status_retrieved?: string
+    pinning_services?: PinningService[]
+    last_republished?: string
+    republish_error?: string
+  }
+
+  export interface PinningService {
+    name: string
+    endpoint: string
+    access_token: string
+    request_id?: string
+    pin_cid?: string
+    pin_status?: string
+    pin_status_retrieved?: string
   }
   
   export interface FollowingPlanet {
@@ -77,6 +99,52 @@ export interface MyPlanet {
     my_planets: MyPlanet[]
     following_planets: FollowingPlanet[]
     selected_view?: SelectedView
+    publish_states: Record<string, PublishState>
+    prewarm_reports: Record<string, PrewarmReport>
+  }
+
+  /** 单个网关、单个页面的预热结果 — 与 Rust GatewayPrewarmResult 一一对应 */
+  export interface GatewayPrewarmResult {
+    gateway: string
+    url: string
+    ok: boolean
+    status?: number
+    elapsed_ms: number
+    error?: string
+  }
+
+  /** 一次发布的网关预热报告 — 与 Rust PrewarmReport 一一对应 */
+  export interface PrewarmReport {
+    planet_id: string
+    cid: string
+    finished_at: string
+    results: GatewayPrewarmResult[]
+  }
+
+  /** 发布阶段 — 与 Rust PublishStage 一一对应 */
+  export type PublishStage =
+    | 'rendering'
+    | 'hashing'
+    | 'adding'
+    | 'publishing_ipns'
+    | 'done'
+    | 'failed'
+
+  /** 发布结果 — 与 Rust PublishResult 一一对应 */
+  export interface PublishResult {
+    cid: string
+    /** CID 与上次发布相同时为 false，表示未实际发布 */
+    published: boolean
+    ipns?: IpfsPublished
+  }
+
+  /** 单个 Planet 的发布状态 — 与 Rust PublishState 一一对应 */
+  export interface PublishState {
+    planet_id: string
+    stage: PublishStage
+    started_at: string
+    stage_started_at: string
+    error?: string
   }
   
   export type SelectedView =
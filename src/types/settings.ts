@@ -0,0 +1,66 @@
+// ============================================================
+// 应用设置类型定义 — 与 Rust AppSettings 一一对应
+// ============================================================
+
+export interface AppSettings {
+  prewarm_gateways: string[]
+  prewarm_timeout_secs: number
+  republish_interval_secs: number
+  ipns_lifetime_secs: number
+  ipns_ttl_secs: number
+  swarm_port: PortPreference
+  api_port: PortPreference
+  gateway_port: PortPreference
+  storage_max_gb: number | null
+  gc_policy: GcPolicy
+  kubo_node: KuboNode
+  /** 自定义 Kubo 可执行文件路径（环境变量 PLANET_KUBO_PATH 优先） */
+  kubo_binary_path: string | null
+  /** 自定义 Kubo 的 SHA-256，设置 kubo_binary_path 时必填 */
+  kubo_binary_sha256: string | null
+  /** 当前使用的网络配置方案名称 */
+  network_profile: string
+  network_profiles: NetworkProfile[]
+}
+
+/** 网络配置方案，内置 low-power / default / server，daemon 重启后生效 */
+export interface NetworkProfile {
+  name: string
+  conn_mgr: {
+    low_water: number
+    high_water: number
+    grace_period_secs: number
+  }
+  peering_peers: { id: string; addrs: string[] }[]
+  /** 域名后缀（如 "eth."）→ DoH 地址 */
+  dns_resolvers: Record<string, string>
+  reprovider_strategy: 'all' | 'pinned' | 'roots'
+  accelerated_dht: boolean
+  relay_client: boolean
+  relay_service: boolean
+}
+
+/** 使用的 Kubo 节点：内置节点或已有的本机 / 远程节点 */
+export type KuboNode =
+  | { kind: 'bundled' }
+  | { kind: 'external'; api: string; gateway: string | null; auth: KuboAuth | null }
+
+/** 外部节点 API 认证，对应 Kubo API.Authorizations */
+export type KuboAuth =
+  | { kind: 'basic'; username: string; password: string }
+  | { kind: 'bearer'; token: string }
+
+/** 自动 GC 策略，任一条件满足即触发 */
+export interface GcPolicy {
+  watermark_percent: number | null
+  interval_secs: number | null
+  after_publishes: number | null
+}
+
+/** daemon 端口选择 — 固定端口 > 上次使用的端口 > 范围扫描 */
+export interface PortPreference {
+  fixed: number | null
+  range_start: number
+  range_end: number
+  last_used: number | null
+}